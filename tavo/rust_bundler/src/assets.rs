@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// Above this size an image/font import is written out as its own file
+/// (see [`EmittedAsset`]) instead of inlined as a base64 data URI, so a
+/// handful of large images don't bloat every page's JS payload.
+const INLINE_THRESHOLD_BYTES: usize = 8 * 1024;
+
+/// How a specifier asked to be resolved, decided by an explicit `?raw`/`?url`
+/// suffix (à la Vite, itself inspired by Dhall's `as Text`/`as Location`
+/// import modes) rather than the imported file's extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// No suffix: resolve normally and let extension-based dispatch decide
+    /// how the file is loaded (code, CSS, or a binary asset).
+    Code,
+    /// `?raw`: the file's contents, exported as a plain string, with no
+    /// parsing or transformation at all.
+    Raw,
+    /// `?url`: only the resolved asset's URL is exported — never inlined,
+    /// even for a file under [`INLINE_THRESHOLD_BYTES`].
+    Url,
+}
+
+/// Strip a trailing `?raw`/`?url` suffix off an import specifier, returning
+/// the bare specifier `AppResolver` can resolve normally plus the mode it
+/// asked for.
+pub fn strip_import_mode_suffix(specifier: &str) -> (&str, ImportMode) {
+    if let Some(bare) = specifier.strip_suffix("?raw") {
+        (bare, ImportMode::Raw)
+    } else if let Some(bare) = specifier.strip_suffix("?url") {
+        (bare, ImportMode::Url)
+    } else {
+        (specifier, ImportMode::Code)
+    }
+}
+
+/// A binary asset (image, font, ...) large enough to be written out as its
+/// own file rather than inlined as a data URI. `url` is the path the
+/// generated module exports and that the emitted file is written under.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EmittedAsset {
+    pub url: String,
+    pub bytes: Vec<u8>,
+}
+
+pub fn is_css(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("css")
+}
+
+pub fn is_css_module(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.ends_with(".module.css"))
+        .unwrap_or(false)
+}
+
+const STATIC_ASSET_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "svg", "webp", "avif", "ico", "woff", "woff2", "ttf", "otf",
+    "eot",
+];
+
+pub fn is_static_asset(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| STATIC_ASSET_EXTENSIONS.iter().any(|known| known.eq_ignore_ascii_case(e)))
+        .unwrap_or(false)
+}
+
+fn mime_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "ico" => "image/x-icon",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "eot" => "application/vnd.ms-fontobject",
+        _ => "application/octet-stream",
+    }
+}
+
+pub fn content_hash(bytes: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// The URL a static asset is reachable under once emitted next to a build's
+/// output, keyed by content hash (like `hydration.rs`'s chunk names) so an
+/// unchanged asset keeps the same URL across rebuilds.
+fn emitted_asset_url(path: &Path, bytes: &[u8]) -> String {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("bin");
+    format!("/_tavo/assets/{}.{}", content_hash(bytes), ext)
+}
+
+fn data_uri(path: &Path, bytes: &[u8]) -> String {
+    format!(
+        "data:{};base64,{}",
+        mime_type(path),
+        base64::encode(bytes)
+    )
+}
+
+/// Resolve a static asset import to the URL its generated module should
+/// export: a base64 data URI when `force_emit` is unset and `bytes` is at
+/// most [`INLINE_THRESHOLD_BYTES`] long, otherwise a path under `/_tavo/assets/`,
+/// recording the asset in `emitted` so the caller writes it out.
+pub fn resolve_asset_url(path: &Path, bytes: &[u8], force_emit: bool, emitted: &mut Vec<EmittedAsset>) -> String {
+    if !force_emit && bytes.len() <= INLINE_THRESHOLD_BYTES {
+        return data_uri(path, bytes);
+    }
+
+    let url = emitted_asset_url(path, bytes);
+    emitted.push(EmittedAsset { url: url.clone(), bytes: bytes.to_vec() });
+    url
+}
+
+/// Side-effecting module for a plain `.css` import: injects its contents as
+/// a `<style>` tag the first time the module is required, the same
+/// behaviour as webpack's/Vite's default style-loader. No default export —
+/// plain CSS imports are for their side effect only.
+pub fn css_side_effect_module(css: &str) -> String {
+    format!(
+        r#"if (typeof document !== "undefined") {{
+  var __tavoStyle = document.createElement("style");
+  __tavoStyle.textContent = {css};
+  document.head.appendChild(__tavoStyle);
+}}
+"#,
+        css = serde_json::to_string(css).expect("string serialization cannot fail")
+    )
+}
+
+/// CSS Modules: every class selector in `css` is rewritten to a
+/// hash-suffixed name unique to this file, injected the same way
+/// [`css_side_effect_module`] does, and the original-name -> hashed-name
+/// mapping is exported as the module's default export so importing code can
+/// look up `styles.button`.
+pub fn css_module(css: &str, path: &Path) -> String {
+    // Hash the path alongside the content so two modules with identical CSS
+    // don't collide on the same generated class names.
+    let hash = content_hash(format!("{}{css}", path.display()).as_bytes());
+    let mut mapping = std::collections::BTreeMap::new();
+
+    let class_re = regex::Regex::new(r"\.([A-Za-z_][A-Za-z0-9_-]*)").expect("valid regex");
+    let rewritten = class_re.replace_all(css, |caps: &regex::Captures| {
+        let original = &caps[1];
+        let hashed = mapping
+            .entry(original.to_string())
+            .or_insert_with(|| format!("{original}_{hash}"))
+            .clone();
+        format!(".{hashed}")
+    });
+
+    let exports = serde_json::to_string(&mapping).expect("map serialization cannot fail");
+
+    format!(
+        "{}\nexport default {};\n",
+        css_side_effect_module(&rewritten),
+        exports
+    )
+}
+
+/// `?raw` mode: `text` exported verbatim as a string, no parsing at all.
+pub fn raw_text_module(text: &str) -> String {
+    format!(
+        "export default {};\n",
+        serde_json::to_string(text).expect("string serialization cannot fail")
+    )
+}
+
+/// `?url` mode (and the fallback export for an extension-dispatched static
+/// asset): only `url` is exported, never the asset's contents.
+pub fn url_module(url: &str) -> String {
+    format!(
+        "export default {};\n",
+        serde_json::to_string(url).expect("string serialization cannot fail")
+    )
+}
+
@@ -1,122 +1,264 @@
-use anyhow::{Result, Context};
-use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
 use std::collections::{HashMap, HashSet};
-use swc_common::{SourceMap, sync::Lrc};
-use swc_ecma_ast::*;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use rayon::prelude::*;
+use swc_bundler::{Bundler as SwcBundler, Config as BundlerConfig, Hook, Load, ModuleData, ModuleRecord, ModuleType, Resolve};
+use swc_common::{sync::Lrc, FileName, Globals, SourceMap, Span, GLOBALS};
+use swc_ecma_ast::{Callee, CallExpr, Expr, KeyValueProp, Lit, Module};
+use swc_ecma_loader::{resolvers::node::NodeModulesResolver, TargetEnv};
+use swc_ecma_visit::{Visit, VisitWith};
+
+use crate::assets::{self, EmittedAsset, ImportMode};
+use crate::compiler::{parse_module_with_filename, TransformSettings};
+use crate::utils::{read_file_content, resolve_import};
+
+/// Prefix marking a `FileName::Custom` specifier as an explicit `?raw`
+/// import: the file at the path following the prefix is loaded as plain
+/// text, exported as a string, with no parsing.
+const RAW_IMPORT_PREFIX: &str = "tavo-raw:";
+/// Prefix marking a `FileName::Custom` specifier as an explicit `?url`
+/// import: only the resolved asset's URL is exported, never its contents.
+const URL_IMPORT_PREFIX: &str = "tavo-url:";
 
-use crate::utils::{resolve_import, read_file_content};
-use crate::compiler::Compiler;
+/// Virtual specifier for the generated hydration wrapper, which has no file
+/// on disk — `AppLoader` serves its source from `virtual_sources` instead of
+/// reading it, and `AppResolver` special-cases it before falling through to
+/// relative/`node_modules` resolution.
+const HYDRATION_ENTRY_SPECIFIER: &str = "tavo:hydration-entry";
 
+/// A single, already-linked bundle produced by `swc_bundler`: one `Module`
+/// with every reachable import resolved, hoisted, and dead-code eliminated,
+/// plus the specifier it was entered from. `SSRRenderer` finds the
+/// component through `module.exports`/`window.App`, not by this name.
+///
+/// Note for anyone tracing an older bug report against a `Bundle.modules:
+/// HashMap<String, String>` iterated in arbitrary order by `SSRRenderer`:
+/// that shape is gone. Bundling an entry now always goes through `Bundler`'s
+/// real `swc_bundler`-backed `link`, which hoists the whole reachable graph
+/// into this one linked `module` rather than handing `ssr::SSRRenderer` a
+/// map of independently-generated sources to concatenate in whatever order
+/// they were visited — so there's no iteration order left to be
+/// nondeterministic about.
 pub struct Bundle {
-    pub modules: HashMap<String, String>,
-    pub entry_point: String,
+    pub module: Module,
+    pub entry_name: String,
+    /// Static assets (images, fonts, ...) discovered while linking that were
+    /// too large to inline as a data URI — see `assets::resolve_asset_url`.
+    pub assets: Vec<EmittedAsset>,
+}
+
+/// The result of linking an entry module for the client: the entry itself,
+/// plus one separately-linked `Module` per dynamic `import()` split point
+/// found in it. Unlike `Bundle`, these split-point modules are NOT inlined
+/// into `entry` — each was handed to `swc_bundler` as its own named entry,
+/// so shared dependencies are deduplicated but the split module's code stays
+/// out of the initial payload.
+pub struct LinkedModules {
+    pub entry: Module,
+    pub entry_name: String,
+    /// `(specifier as written in the `import()` call, the linked module it
+    /// resolved to)`.
+    pub dynamic: Vec<(String, Module)>,
+    /// Static assets discovered while linking the entry and every dynamic
+    /// chunk — see `Bundle::assets`.
+    pub assets: Vec<EmittedAsset>,
 }
 
 pub struct Bundler {
     source_map: Lrc<SourceMap>,
-    compiler: Compiler,
+    transform_settings: TransformSettings,
 }
 
 impl Bundler {
-    pub fn new(source_map: Lrc<SourceMap>) -> Result<Self> {
-        Ok(Self {
-            source_map,
-            compiler: Compiler::new()?,
-        })
+    pub fn new(source_map: Lrc<SourceMap>, transform_settings: TransformSettings) -> Result<Self> {
+        Ok(Self { source_map, transform_settings })
     }
-    
+
     pub async fn bundle_for_ssr(&mut self, entry: &Path) -> Result<Bundle> {
-        let mut modules = HashMap::new();
-        let mut visited = HashSet::new();
-        
-        self.collect_dependencies(entry, &mut modules, &mut visited).await?;
-        
-        Ok(Bundle {
-            modules,
-            entry_point: entry.to_string_lossy().to_string(),
-        })
-    }
-    
-    pub async fn bundle_for_hydration(&mut self, entry: &Path) -> Result<Bundle> {
-        let mut modules = HashMap::new();
-        let mut visited = HashSet::new();
-        
-        // Add hydration wrapper
-        let hydration_code = self.generate_hydration_wrapper(entry)?;
-        modules.insert("__hydration_entry__".to_string(), hydration_code);
-        
-        self.collect_dependencies(entry, &mut modules, &mut visited).await?;
-        
+        let absolute = entry
+            .canonicalize()
+            .with_context(|| format!("Failed to canonicalize entry: {}", entry.display()))?;
+
+        // The server always renders the full tree synchronously, so dynamic
+        // `import()` split points are irrelevant here — only the client
+        // bundle needs them kept out of the initial payload.
+        let linked = self.link(FileName::Real(absolute), HashMap::new())?;
+
         Ok(Bundle {
-            modules,
-            entry_point: "__hydration_entry__".to_string(),
+            module: linked.entry,
+            entry_name: linked.entry_name,
+            assets: linked.assets,
         })
     }
-    
-    async fn collect_dependencies(
-        &mut self,
-        file_path: &Path,
-        modules: &mut HashMap<String, String>,
-        visited: &mut HashSet<PathBuf>,
-    ) -> Result<()> {
-        let absolute_path = file_path.canonicalize()
-            .context("Failed to canonicalize path")?;
-        
-        if visited.contains(&absolute_path) {
-            return Ok(());
-        }
-        visited.insert(absolute_path.clone());
-        
-        let content = read_file_content(&absolute_path)?;
-        let module = self.compiler.parse_tsx(&content, &absolute_path.to_string_lossy())?;
-        
-        // Extract imports
-        let imports = self.extract_imports(&module);
-        
-        // Transform the module
-        let transformed = self.compiler.transform_tsx(module)?;
-        let code = self.compiler.generate_code(&transformed)?;
-        
-        modules.insert(absolute_path.to_string_lossy().to_string(), code);
-        
-        // Recursively process imports
-        for import_path in imports {
-            if let Ok(resolved_path) = resolve_import(&import_path, &absolute_path) {
-                self.collect_dependencies(&resolved_path, modules, visited).await?;
-            }
-        }
-        
-        Ok(())
+
+    pub async fn bundle_for_hydration(&mut self, entry: &Path) -> Result<LinkedModules> {
+        let absolute = entry
+            .canonicalize()
+            .with_context(|| format!("Failed to canonicalize entry: {}", entry.display()))?;
+
+        let wrapper_code = self.generate_hydration_wrapper(&absolute)?;
+        let entry_specifier = FileName::Custom(HYDRATION_ENTRY_SPECIFIER.to_string());
+
+        let mut virtual_sources = HashMap::new();
+        virtual_sources.insert(entry_specifier.clone(), wrapper_code);
+
+        self.link(entry_specifier, virtual_sources)
     }
-    
-    fn extract_imports(&self, module: &Module) -> Vec<String> {
-        let mut imports = Vec::new();
-        
-        for item in &module.body {
-            match item {
-                ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
-                    imports.push(import_decl.src.value.to_string());
-                }
-                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export_all)) => {
-                    imports.push(export_all.src.value.to_string());
-                }
-                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export_named)) => {
-                    if let Some(src) = &export_named.src {
-                        imports.push(src.value.to_string());
+
+    /// Drive a real `swc_bundler::Bundler` from `entry_specifier` to a linked
+    /// `Module`, instead of walking imports by hand and concatenating each
+    /// module's emitted JS as text. Dynamic `import()` calls found in the
+    /// entry are handed to `swc_bundler` as their own named entries so their
+    /// target modules come back as separate, still-linked bundles instead of
+    /// being inlined into `entry`.
+    ///
+    /// Discovering those dynamic-import targets is our own transitive
+    /// pre-walk (the loop below) ahead of handing `entries` to
+    /// `swc_bundler` — each resolve + load + transform is real,
+    /// module-at-a-time work, and one BFS depth's worth of it runs
+    /// concurrently via `rayon` rather than one module at a time.
+    fn link(
+        &self,
+        entry_specifier: FileName,
+        virtual_sources: HashMap<FileName, String>,
+    ) -> Result<LinkedModules> {
+        let globals = Globals::new();
+
+        GLOBALS.set(&globals, || {
+            let assets = Arc::new(Mutex::new(Vec::new()));
+            let loader = AppLoader {
+                source_map: self.source_map.clone(),
+                virtual_sources,
+                assets: assets.clone(),
+                transform_settings: self.transform_settings.clone(),
+            };
+            let resolver = AppResolver::new();
+
+            let entry_data = loader
+                .load(&entry_specifier)
+                .context("Failed to load the entry module")?;
+
+            // Discover dynamic `import()` targets transitively: a
+            // lazy-loaded chunk can itself `import()` another one, and that
+            // nested target needs to become its own named `swc_bundler`
+            // entry too, or it'd get inlined into whichever chunk happened
+            // to load it first instead of staying a separate, on-demand
+            // chunk.
+            let mut entries = HashMap::new();
+            entries.insert("main".to_string(), entry_specifier.clone());
+
+            let mut dynamic_specifiers = Vec::new();
+            let mut seen = HashSet::new();
+            let mut frontier: Vec<(FileName, String)> = find_dynamic_imports(&entry_data.module)
+                .into_iter()
+                .inspect(|s| {
+                    seen.insert(s.clone());
+                })
+                .map(|s| (entry_specifier.clone(), s))
+                .collect();
+
+            // Resolve + load + transform one whole BFS depth at a time in
+            // parallel: every `(base, specifier)` pair in `frontier` is
+            // independent of the others at the same depth, so there's no
+            // reason to parse/transform them one module at a time on a
+            // single thread. `GLOBALS` is thread-local, so each task re-sets
+            // it for its own worker thread before touching anything
+            // (`resolve`/`load`'s `transform_module` call) that needs an
+            // active `Mark::new()` scope.
+            while !frontier.is_empty() {
+                let loaded: Vec<Result<(String, FileName, ModuleData)>> = frontier
+                    .par_iter()
+                    .map(|(base, specifier)| {
+                        GLOBALS.set(&globals, || {
+                            let resolved = resolver.resolve(base, specifier).with_context(|| {
+                                format!("Failed to resolve dynamic import '{}'", specifier)
+                            })?;
+                            let chunk_data = loader.load(&resolved).with_context(|| {
+                                format!("Failed to load dynamic import target '{}'", specifier)
+                            })?;
+                            Ok((specifier.clone(), resolved, chunk_data))
+                        })
+                    })
+                    .collect();
+
+                let mut next_frontier = Vec::new();
+                for result in loaded {
+                    let (specifier, resolved, chunk_data) = result?;
+
+                    for nested in find_dynamic_imports(&chunk_data.module) {
+                        if seen.insert(nested.clone()) {
+                            next_frontier.push((resolved.clone(), nested));
+                        }
                     }
+
+                    entries.insert(specifier.clone(), resolved);
+                    dynamic_specifiers.push(specifier);
                 }
-                _ => {}
+                frontier = next_frontier;
             }
-        }
-        
-        imports
+
+            let mut swc_bundler = SwcBundler::new(
+                &globals,
+                self.source_map.clone(),
+                loader,
+                resolver,
+                BundlerConfig {
+                    module: ModuleType::Es,
+                    require: true,
+                    ..Default::default()
+                },
+                Box::new(NoopHook),
+            );
+
+            let bundles = swc_bundler
+                .bundle(entries)
+                .context("swc_bundler failed to link the module graph")?;
+
+            // Drop `swc_bundler` (and the `loader`/`assets` clone it holds)
+            // before reclaiming `assets` below, so the `Arc` has exactly one
+            // owner left.
+            drop(swc_bundler);
+
+            let mut by_name: HashMap<String, Module> =
+                bundles.into_iter().map(|b| (b.name, b.module)).collect();
+
+            let entry = by_name
+                .remove("main")
+                .context("swc_bundler didn't return a bundle for the `main` entry")?;
+
+            let dynamic = dynamic_specifiers
+                .into_iter()
+                .filter_map(|specifier| by_name.remove(&specifier).map(|m| (specifier, m)))
+                .collect();
+
+            let assets = Arc::try_unwrap(assets)
+                .map(|cell| cell.into_inner().expect("assets lock poisoned"))
+                .unwrap_or_default();
+
+            Ok(LinkedModules {
+                entry,
+                entry_name: file_name_to_string(&entry_specifier),
+                dynamic,
+                assets,
+            })
+        })
     }
-    
+
+    /// `hydrateRoot` here is the real `react-dom/client` export resolved by
+    /// `AppResolver` from `node_modules` — it already walks the existing DOM
+    /// under `#root` in lockstep with the element tree and only patches
+    /// divergences, so there's no hand-rolled reconciliation to get right.
+    /// `onRecoverableError` is the one hook worth wiring ourselves: without
+    /// it a hydration mismatch is silently recovered from client-side with
+    /// nothing surfaced, which makes a markup divergence from
+    /// `SSRRenderer::render` invisible during development.
     fn generate_hydration_wrapper(&self, entry: &Path) -> Result<String> {
-        let entry_name = entry.file_stem()
+        let entry_name = entry
+            .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("App");
-        
+
         let wrapper = format!(
             r#"
 import React from "react";
@@ -125,14 +267,229 @@ import {} from "{}";
 
 const rootElement = document.getElementById("root");
 if (rootElement) {{
-    hydrateRoot(rootElement, React.createElement({}));
+    hydrateRoot(rootElement, React.createElement({}), {{
+        onRecoverableError(error, errorInfo) {{
+            console.error("Hydration mismatch:", error, errorInfo?.componentStack);
+        }},
+    }});
 }}
 "#,
             entry_name,
             entry.to_string_lossy(),
             entry_name
         );
-        
+
         Ok(wrapper)
     }
-}
\ No newline at end of file
+}
+
+/// `swc_bundler::Load` impl that parses each module through the same
+/// `parse_module_with_filename` as `Compiler::parse_tsx`, lexing against the
+/// tsconfig's target rather than `parse_tsx`'s fixed ES2022. Most specifiers come from disk,
+/// dispatched by extension: `.tsx`/`.ts`/`.jsx`/`.js` parse as code, then go
+/// through `compiler::transform_module` (TS strip + JSX + `preset_env`) so
+/// `swc_bundler` links already-plain JS; `.css` becomes an injected `<style>`
+/// (with CSS-Modules class hashing for `*.module.css`), and images/fonts
+/// become a module exporting their URL (inlined as a data URI under a size
+/// threshold, otherwise recorded in `assets` for the caller to emit as a
+/// file) — neither needs the transform, since both are already synthesized
+/// plain JS. A `tavo-raw:`/`tavo-url:`-prefixed `FileName::Custom` (see
+/// `AppResolver`) forces the `?raw`/`?url` import mode regardless of
+/// extension, same no-transform rule. Anything else not a real file falls
+/// back to `virtual_sources` (currently just the generated hydration
+/// wrapper, which is already plain JS + ESM but still passed through the
+/// transform so its imports get the same resolver pass as everything else).
+struct AppLoader {
+    source_map: Lrc<SourceMap>,
+    virtual_sources: HashMap<FileName, String>,
+    /// `Arc<Mutex<...>>` rather than `Rc<RefCell<...>>` so `&AppLoader` is
+    /// `Sync` — `Bundler::link`'s dynamic-import discovery loop shares one
+    /// `AppLoader` across a `rayon` parallel iterator.
+    assets: Arc<Mutex<Vec<EmittedAsset>>>,
+    transform_settings: TransformSettings,
+}
+
+impl Load for AppLoader {
+    fn load(&self, file: &FileName) -> Result<ModuleData> {
+        let (code, needs_transform) = match file {
+            FileName::Real(path) => self.load_real_file(path)?,
+            FileName::Custom(specifier) if specifier.starts_with(RAW_IMPORT_PREFIX) => {
+                let path = PathBuf::from(&specifier[RAW_IMPORT_PREFIX.len()..]);
+                let text = read_file_content(&path)?;
+                (assets::raw_text_module(&text), false)
+            }
+            FileName::Custom(specifier) if specifier.starts_with(URL_IMPORT_PREFIX) => {
+                let path = PathBuf::from(&specifier[URL_IMPORT_PREFIX.len()..]);
+                let bytes = std::fs::read(&path)
+                    .with_context(|| format!("Failed to read asset: {}", path.display()))?;
+                let url = assets::resolve_asset_url(&path, &bytes, true, &mut self.assets.lock().expect("assets lock poisoned"));
+                (assets::url_module(&url), false)
+            }
+            FileName::Custom(_) => (
+                self.virtual_sources
+                    .get(file)
+                    .cloned()
+                    .with_context(|| format!("No virtual source registered for {:?}", file))?,
+                true,
+            ),
+            other => return Err(anyhow::anyhow!("Unsupported module source: {:?}", other)),
+        };
+
+        let (fm, module) = parse_module_with_filename(
+            &self.source_map,
+            &code,
+            file.clone(),
+            self.transform_settings.tsconfig.target,
+        )?;
+        let module = if needs_transform {
+            crate::compiler::transform_module(module, &self.source_map, &self.transform_settings)?
+        } else {
+            module
+        };
+
+        Ok(ModuleData {
+            fm,
+            module,
+            comments: Default::default(),
+        })
+    }
+}
+
+impl AppLoader {
+    /// Returns the module's source plus whether it still needs
+    /// `compiler::transform_module` — true for real `.ts`/`.tsx`/`.js`/
+    /// `.jsx` source, false for the CSS/asset branches, which already
+    /// produce synthesized plain JS with nothing left to strip or
+    /// transform.
+    fn load_real_file(&self, path: &Path) -> Result<(String, bool)> {
+        if assets::is_css(path) {
+            let css = read_file_content(path)?;
+            return Ok((
+                if assets::is_css_module(path) {
+                    assets::css_module(&css, path)
+                } else {
+                    assets::css_side_effect_module(&css)
+                },
+                false,
+            ));
+        }
+
+        if assets::is_static_asset(path) {
+            let bytes = std::fs::read(path)
+                .with_context(|| format!("Failed to read asset: {}", path.display()))?;
+            let url = assets::resolve_asset_url(path, &bytes, false, &mut self.assets.lock().expect("assets lock poisoned"));
+            return Ok((assets::url_module(&url), false));
+        }
+
+        Ok((read_file_content(path)?, true))
+    }
+}
+
+/// `swc_bundler::Resolve` impl covering the two ways a specifier shows up in
+/// this codebase: relative imports resolved the same way `resolve_import`
+/// already resolves them for the rest of the crate (including `app/`'s
+/// `page.tsx`/`layout.tsx` file-based routing), and bare specifiers (`react`,
+/// `react-dom/client`, ...) resolved from `node_modules` as a browser target
+/// would see them.
+struct AppResolver {
+    node_resolver: NodeModulesResolver,
+}
+
+impl AppResolver {
+    fn new() -> Self {
+        Self {
+            node_resolver: NodeModulesResolver::new(TargetEnv::Browser, Default::default(), true),
+        }
+    }
+}
+
+impl Resolve for AppResolver {
+    fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<FileName> {
+        if module_specifier == HYDRATION_ENTRY_SPECIFIER {
+            return Ok(FileName::Custom(module_specifier.to_string()));
+        }
+
+        let (bare_specifier, mode) = assets::strip_import_mode_suffix(module_specifier);
+
+        let resolved = if bare_specifier.starts_with("./") || bare_specifier.starts_with("../") {
+            let base_path = match base {
+                FileName::Real(path) => path.clone(),
+                // The hydration wrapper has no real path of its own; a
+                // relative import from it (there are none today) resolves
+                // against the current working directory.
+                FileName::Custom(_) => PathBuf::from("."),
+                _ => return Err(anyhow::anyhow!("Unsupported base file name: {:?}", base)),
+            };
+
+            FileName::Real(resolve_import(bare_specifier, &base_path)?)
+        } else {
+            self.node_resolver
+                .resolve(base, bare_specifier)
+                .with_context(|| format!("Failed to resolve '{}' from node_modules", bare_specifier))?
+        };
+
+        // `?raw`/`?url` only make sense against a real file on disk; a
+        // specifier that resolved to something else (the hydration entry,
+        // say) just ignores the suffix.
+        match (mode, resolved) {
+            (ImportMode::Raw, FileName::Real(path)) => {
+                Ok(FileName::Custom(format!("{RAW_IMPORT_PREFIX}{}", path.display())))
+            }
+            (ImportMode::Url, FileName::Real(path)) => {
+                Ok(FileName::Custom(format!("{URL_IMPORT_PREFIX}{}", path.display())))
+            }
+            (_, resolved) => Ok(resolved),
+        }
+    }
+}
+
+/// `swc_bundler` requires a `Hook` to fill in `import.meta` properties; this
+/// codebase doesn't use `import.meta`, so there are none to provide.
+struct NoopHook;
+
+impl Hook for NoopHook {
+    fn get_import_meta_props(
+        &self,
+        _span: Span,
+        _module_record: &ModuleRecord,
+    ) -> Result<Vec<KeyValueProp>> {
+        Ok(vec![])
+    }
+}
+
+fn file_name_to_string(file_name: &FileName) -> String {
+    match file_name {
+        FileName::Real(path) => path.to_string_lossy().to_string(),
+        FileName::Custom(specifier) => specifier.clone(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Walks `module` for `import(...)` call expressions with a single string
+/// literal argument — the only shape `AppResolver` can resolve ahead of
+/// time, since anything else (a computed specifier, a template literal)
+/// can't be split out without running the code.
+fn find_dynamic_imports(module: &Module) -> Vec<String> {
+    let mut collector = DynamicImportCollector {
+        specifiers: Vec::new(),
+    };
+    module.visit_with(&mut collector);
+    collector.specifiers
+}
+
+struct DynamicImportCollector {
+    specifiers: Vec<String>,
+}
+
+impl Visit for DynamicImportCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Import(_) = &call.callee {
+            if let Some(arg) = call.args.first() {
+                if let Expr::Lit(Lit::Str(s)) = &*arg.expr {
+                    self.specifiers.push(s.value.to_string());
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+/// Sidecar recording the dependency set that was folded into a cached
+/// module's key. The key itself is what changes when a transitive
+/// dependency does (see `DiskCache::key`); `get` returns this record but
+/// does not check it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CacheMeta {
+    pub specifier: String,
+    pub dependencies: Vec<String>,
+}
+
+/// Persistent on-disk compilation cache keyed by a hash of a module's source,
+/// its resolved dependency specifiers, and the active transpile options.
+/// Modeled on Deno's `DiskCache`: each entry is the emitted JS plus an
+/// optional source map and a `.meta.json` sidecar.
+pub struct DiskCache {
+    root: PathBuf,
+}
+
+impl DiskCache {
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        std::fs::create_dir_all(&root)
+            .with_context(|| format!("Failed to create cache directory: {}", root.display()))?;
+        Ok(Self { root })
+    }
+
+    /// Stable hash of `content`, the resolved `dependencies`, and an
+    /// `options_fingerprint` string the caller derives from whatever
+    /// transpile options are active (tsconfig target/runtime, minify, etc.).
+    pub fn key(content: &str, dependencies: &[String], options_fingerprint: &str) -> String {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        dependencies.hash(&mut hasher);
+        options_fingerprint.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    fn emit_path(&self, key: &str) -> PathBuf {
+        self.root.join(format!("{key}.js"))
+    }
+
+    fn sourcemap_path(&self, key: &str) -> PathBuf {
+        self.root.join(format!("{key}.js.map"))
+    }
+
+    fn meta_path(&self, key: &str) -> PathBuf {
+        self.root.join(format!("{key}.meta.json"))
+    }
+
+    /// Look up a cache entry, returning the cached emit, its source map (if
+    /// one was cached alongside it), and its dependency metadata. `None` on
+    /// any miss (including a corrupt sidecar), so a cache-read failure always
+    /// just falls back to recompiling.
+    pub fn get(&self, key: &str) -> Option<(String, Option<String>, CacheMeta)> {
+        let emit = std::fs::read_to_string(self.emit_path(key)).ok()?;
+        let meta_raw = std::fs::read_to_string(self.meta_path(key)).ok()?;
+        let meta: CacheMeta = serde_json::from_str(&meta_raw).ok()?;
+        let sourcemap = std::fs::read_to_string(self.sourcemap_path(key)).ok();
+        Some((emit, sourcemap, meta))
+    }
+
+    pub fn put(
+        &self,
+        key: &str,
+        emit: &str,
+        sourcemap: Option<&str>,
+        meta: &CacheMeta,
+    ) -> Result<()> {
+        std::fs::write(self.emit_path(key), emit)
+            .with_context(|| format!("Failed to write cache entry: {key}"))?;
+        if let Some(map) = sourcemap {
+            std::fs::write(self.sourcemap_path(key), map)?;
+        }
+        std::fs::write(self.meta_path(key), serde_json::to_string_pretty(meta)?)?;
+        Ok(())
+    }
+}
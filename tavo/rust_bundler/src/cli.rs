@@ -5,9 +5,9 @@ use std::path::PathBuf;
 #[command(name = "myssr")]
 #[command(about = "A Rust-based SSR + Hydration compiler for React components using SWC")]
 pub struct Args {
-    /// Entry point file (e.g., src/App.tsx)
+    /// Entry point file (e.g., src/App.tsx). Required unless `--dev` is set.
     #[arg(long, value_name = "FILE")]
-    pub entry: PathBuf,
+    pub entry: Option<PathBuf>,
     
     /// Generate SSR HTML output
     #[arg(long)]
@@ -20,4 +20,61 @@ pub struct Args {
     /// Output file path
     #[arg(long, short, value_name = "FILE")]
     pub out: Option<PathBuf>,
+
+    /// Minify the emitted JavaScript (compress + mangle, then minified
+    /// codegen) and the HTML document produced by `--ssr --hydrate --out`
+    #[arg(long)]
+    pub minify: bool,
+
+    /// Gzip- and brotli-compress emitted artifacts, writing `.gz`/`.br`
+    /// siblings next to `--out` (and next to each hydration chunk) so a
+    /// static host can serve precompressed responses
+    #[arg(long)]
+    pub compress: bool,
+
+    /// Emit a source map alongside the output
+    #[arg(long)]
+    pub sourcemap: bool,
+
+    /// Embed the source map as a `//# sourceMappingURL=data:...` comment
+    /// instead of writing it as a sibling `.map` file. Only applies when
+    /// `--sourcemap` is set.
+    #[arg(long)]
+    pub inline_sourcemap: bool,
+
+    /// Browserslist-style query (e.g. "defaults", "> 0.5%") to transpile and
+    /// polyfill for. Omit to skip the `preset_env` pass entirely.
+    #[arg(long, value_name = "QUERY")]
+    pub targets: Option<String>,
+
+    /// `core-js` major version used for injected polyfill imports
+    #[arg(long, default_value_t = 3.0)]
+    pub corejs: f64,
+
+    /// Use `preset_env`'s entry mode (replace `import "core-js"`) instead of
+    /// the default usage-scanning mode
+    #[arg(long)]
+    pub preset_env_entry: bool,
+
+    /// Directory for the persistent on-disk compilation cache
+    #[arg(long, value_name = "DIR", default_value = ".tavo-cache")]
+    pub cache_dir: PathBuf,
+
+    /// Disable the on-disk compilation cache and always recompile
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Start the long-lived dev server instead of a one-shot build: watches
+    /// `--app-dir` and pushes rebuilt routes to connected browsers over a
+    /// WebSocket. Ignores `--entry`/`--ssr`/`--hydrate`/`--out`.
+    #[arg(long)]
+    pub dev: bool,
+
+    /// App directory `tavo dev` watches and resolves routes against
+    #[arg(long, value_name = "DIR", default_value = "app")]
+    pub app_dir: PathBuf,
+
+    /// Port the dev server's WebSocket endpoint listens on
+    #[arg(long, default_value_t = 3001)]
+    pub port: u16,
 }
\ No newline at end of file
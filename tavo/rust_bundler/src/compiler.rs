@@ -1,6 +1,6 @@
 use anyhow::{Result, Context};
 use std::path::{Path, PathBuf};
-use std::collections::HashMap;
+use swc_common::errors::Handler;
 use swc_common::{Globals, Mark, GLOBALS};
 use swc_ecma_ast::*;
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
@@ -11,41 +11,215 @@ use swc_ecma_transforms::{
 };
 use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
 use swc_common::{SourceMap, sync::Lrc};
+use swc_ecma_minifier::{optimize, ExtraOptions, MinifyOptions};
+use swc_ecma_preset_env::{
+    preset_env, Config as PresetEnvConfig, Mode as PresetEnvMode, Query as PresetEnvQuery,
+    Targets as PresetEnvTargets,
+};
+use regex::Regex;
 
 use crate::bundler::Bundler;
+use crate::cache::{CacheMeta, DiskCache};
+use crate::error::{CollectingEmitter, DiagnosticItem, SSRError};
 use crate::ssr::SSRRenderer;
-use crate::hydration::HydrationGenerator;
-use crate::utils::resolve_import;
+use crate::hydration::{HydrationGenerator, HydrationOutput};
+use crate::utils::{read_file_content, resolve_import};
 
 pub struct Compiler {
     source_map: Lrc<SourceMap>,
     bundler: Bundler,
     ssr_renderer: SSRRenderer,
     hydration_generator: HydrationGenerator,
+    /// Production build toggle: when set, `generate_code` runs the emitted
+    /// `Module` through `swc_ecma_minifier` (compress + mangle) before
+    /// minified codegen, and `combine_html_and_script` minifies the HTML
+    /// document it produces. Off by default so a plain dev build stays
+    /// readable. See `Compiler::with_minify`.
+    pub(crate) minify: bool,
+    /// `--sourcemap`: when set, `generate_code` builds a source map from the
+    /// emitted JS's raw mappings alongside the code itself. See
+    /// `Compiler::with_sourcemap`.
+    pub(crate) sourcemap: bool,
+    /// `--inline-sourcemap`: embed the map as a `//# sourceMappingURL=
+    /// data:...` comment on the code instead of returning it separately for
+    /// the caller to write out as a sibling `.map` file. Only meaningful
+    /// when `sourcemap` is set.
+    pub(crate) inline_sourcemap: bool,
+    /// Kept alongside `bundler`'s own copy purely to fingerprint the active
+    /// transform options into the cache key — see `Compiler::with_cache`.
+    transform_settings: TransformSettings,
+    /// `--cache-dir`/`--no-cache`: when set, `render_ssr` and
+    /// `generate_hydration_script` look up (and populate) a cache entry
+    /// before doing the real bundle/transform/codegen work. `None` when
+    /// `--no-cache` was passed, same as never calling `with_cache` at all.
+    /// See `Compiler::with_cache`.
+    cache: Option<DiskCache>,
 }
 
 impl Compiler {
     pub fn new() -> Result<Self> {
+        Self::with_transform_settings(TransformSettings::default())
+    }
+
+    /// Like `Compiler::new`, but with `--targets`/`--corejs`/
+    /// `--preset-env-entry` resolved into `settings` instead of the
+    /// `preset_env`-disabled default — see `TransformSettings`.
+    pub fn with_transform_settings(settings: TransformSettings) -> Result<Self> {
         let source_map = Lrc::new(SourceMap::default());
-        
+
         Ok(Self {
             source_map: source_map.clone(),
-            bundler: Bundler::new(source_map.clone())?,
-            ssr_renderer: SSRRenderer::new()?,
-            hydration_generator: HydrationGenerator::new(source_map.clone())?,
+            bundler: Bundler::new(source_map.clone(), settings.clone())?,
+            ssr_renderer: SSRRenderer::new(source_map.clone())?,
+            hydration_generator: HydrationGenerator::new()?,
+            minify: false,
+            sourcemap: false,
+            inline_sourcemap: false,
+            transform_settings: settings,
+            cache: None,
         })
     }
-    
-    pub async fn render_ssr(&mut self, entry: &Path) -> Result<String> {
+
+    /// Opt into the production build profile: minified JS from
+    /// `generate_code` (and, transitively, from the hydration bundle it
+    /// backs) and a minified HTML document from `combine_html_and_script`.
+    pub fn with_minify(mut self, minify: bool) -> Self {
+        self.minify = minify;
+        self.hydration_generator.set_minify(minify);
+        self
+    }
+
+    /// Opt into emitting a source map alongside every hydration bundle
+    /// `generate_code` produces. `inline` embeds it as a data-URI comment on
+    /// the code instead of returning it for the caller to write as a
+    /// sibling `.map` file — see `Compiler::generate_code`.
+    pub fn with_sourcemap(mut self, sourcemap: bool, inline: bool) -> Self {
+        self.sourcemap = sourcemap;
+        self.inline_sourcemap = inline;
+        self.hydration_generator.set_sourcemap(sourcemap, inline);
+        self
+    }
+
+    /// Opt into the persistent on-disk compilation cache at `cache_dir`:
+    /// `render_ssr`/`generate_hydration_script` key each entry's cache
+    /// lookup on its source content, the content of every file reachable
+    /// from it through its scraped import specifiers (see
+    /// `static_import_specifiers`), and every active
+    /// transform/minify/sourcemap setting, skipping the real bundle/render
+    /// work entirely on a hit. `no_cache` (`--no-cache`) disables this
+    /// outright, the same as never calling `with_cache` at all.
+    pub fn with_cache(mut self, cache_dir: &Path, no_cache: bool) -> Result<Self> {
+        self.cache = if no_cache {
+            None
+        } else {
+            Some(DiskCache::new(cache_dir)?)
+        };
+        Ok(self)
+    }
+
+    /// Renders `entry` to HTML, alongside any static assets (images, fonts,
+    /// ...) discovered while bundling it that were too large to inline as a
+    /// data URI and so need writing out next to the HTML — see
+    /// `bundler::Bundle::assets`.
+    pub async fn render_ssr(&mut self, entry: &Path) -> Result<(String, Vec<crate::assets::EmittedAsset>)> {
+        let cache_key = self.cache_key_for(entry, "ssr")?;
+
+        if let Some((key, _)) = &cache_key {
+            if let Some(result) = self.load_from_cache(key) {
+                return Ok(result);
+            }
+        }
+
         let bundle = self.bundler.bundle_for_ssr(entry).await?;
-        self.ssr_renderer.render(&bundle).await
+        let html = self.ssr_renderer.render(&bundle).await?;
+        let result = (html, bundle.assets);
+
+        if let Some((key, dependencies)) = &cache_key {
+            self.store_in_cache(key, entry, dependencies, &result);
+        }
+
+        Ok(result)
     }
-    
-    pub async fn generate_hydration_script(&mut self, entry: &Path) -> Result<String> {
-        let bundle = self.bundler.bundle_for_hydration(entry).await?;
-        self.hydration_generator.generate(&bundle).await
+
+    pub async fn generate_hydration_script(&mut self, entry: &Path) -> Result<HydrationOutput> {
+        let cache_key = self.cache_key_for(entry, "hydration")?;
+
+        if let Some((key, _)) = &cache_key {
+            if let Some(output) = self.load_from_cache(key) {
+                return Ok(output);
+            }
+        }
+
+        let linked = self.bundler.bundle_for_hydration(entry).await?;
+        let output = self.hydration_generator.generate(&linked).await?;
+
+        if let Some((key, dependencies)) = &cache_key {
+            self.store_in_cache(key, entry, dependencies, &output);
+        }
+
+        Ok(output)
     }
-    
+
+    /// Computes `entry`'s cache key (and the dependency records folded into
+    /// it, kept around for `CacheMeta`) from its source content, the
+    /// content of every file transitively reachable from it through
+    /// `static_import_specifiers` + `resolve_import` (each recorded as
+    /// `path@hash`), a `kind` tag that keeps `render_ssr`'s and
+    /// `generate_hydration_script`'s entries apart, and every active
+    /// transform/minify/sourcemap setting — or `None` when `--no-cache`/no
+    /// `with_cache` call is in effect.
+    fn cache_key_for(&self, entry: &Path, kind: &str) -> Result<Option<(String, Vec<String>)>> {
+        if self.cache.is_none() {
+            return Ok(None);
+        }
+
+        let source = read_file_content(entry)?;
+        let mut dependencies = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        let mut pending = vec![(entry.to_path_buf(), source.clone())];
+        while let Some((file, code)) = pending.pop() {
+            for specifier in static_import_specifiers(&code) {
+                let Ok(path) = resolve_import(&specifier, &file) else { continue };
+                if !seen.insert(path.clone()) {
+                    continue;
+                }
+                let Ok(dep_source) = read_file_content(&path) else { continue };
+                dependencies.push(format!("{}@{}", path.display(), DiskCache::key(&dep_source, &[], "")));
+                pending.push((path, dep_source));
+            }
+        }
+        let fingerprint = format!(
+            "{}|{:?}|minify={}|sourcemap={}|inline_sourcemap={}",
+            kind, self.transform_settings, self.minify, self.sourcemap, self.inline_sourcemap
+        );
+        let key = DiskCache::key(&source, &dependencies, &fingerprint);
+
+        Ok(Some((key, dependencies)))
+    }
+
+    fn load_from_cache<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let (cached, _map, _meta) = self.cache.as_ref()?.get(key)?;
+        serde_json::from_str(&cached).ok()
+    }
+
+    /// Best-effort cache population: a write failure (e.g. a read-only
+    /// `--cache-dir`) just means the next build recompiles too, so it's
+    /// logged rather than surfaced as a hard error of the build that
+    /// produced `result` just fine.
+    fn store_in_cache<T: serde::Serialize>(&self, key: &str, entry: &Path, dependencies: &[String], result: &T) {
+        let Some(cache) = &self.cache else { return };
+        let Ok(serialized) = serde_json::to_string(result) else {
+            return;
+        };
+        let meta = CacheMeta {
+            specifier: entry.to_string_lossy().to_string(),
+            dependencies: dependencies.to_vec(),
+        };
+        if let Err(err) = cache.put(key, &serialized, None, &meta) {
+            eprintln!("tavo: failed to write cache entry for {}: {:#}", entry.display(), err);
+        }
+    }
+
     pub fn combine_html_and_script(&self, html: &str, js: &str) -> Result<String> {
         let full_html = format!(
             r#"<!DOCTYPE html>
@@ -62,85 +236,357 @@ impl Compiler {
 </html>"#,
             html, js
         );
-        
-        Ok(full_html)
+
+        if self.minify {
+            Ok(minify_html_document(&full_html))
+        } else {
+            Ok(full_html)
+        }
     }
     
     pub fn parse_tsx(&self, code: &str, filename: &str) -> Result<Module> {
-        let source_file = self.source_map.new_source_file(
+        let (_fm, module) = parse_module_with_filename(
+            &self.source_map,
+            code,
             swc_common::FileName::Real(PathBuf::from(filename)),
-            code.to_string(),
-        );
-        
-        let lexer = Lexer::new(
-            Syntax::Typescript(TsConfig {
-                tsx: true,
-                decorators: false,
-                dts: false,
-                no_early_errors: true,
-                disallow_ambiguous_jsx_like: false,
-            }),
             EsVersion::Es2022,
-            StringInput::from(&*source_file),
-            None,
-        );
-        
-        let mut parser = Parser::new_from(lexer);
-        
-        parser
-            .parse_module()
-            .map_err(|e| anyhow::anyhow!("Parse error: {:?}", e))
+        )?;
+        Ok(module)
     }
-    
-    pub fn transform_tsx(&self, mut module: Module) -> Result<Module> {
+
+    /// Strips TypeScript types and JSX to plain `React.createElement` calls
+    /// with the classic-runtime, no-`preset_env` defaults. Standalone
+    /// callers only; `AppLoader` (bundler.rs) calls `transform_module`
+    /// directly with the bundle's real `TransformSettings` so `--targets`/
+    /// `--corejs` actually reach what gets bundled.
+    pub fn transform_tsx(&self, module: Module) -> Result<Module> {
         GLOBALS.set(&Globals::new(), || {
-            let unresolved_mark = Mark::new();
-            let top_level_mark = Mark::new();
-            
-            // Apply resolver first
-            module = module.fold_with(&mut resolver(unresolved_mark, top_level_mark, true));
-            
-            // Strip TypeScript types
-            module = module.fold_with(&mut strip(top_level_mark));
-            
-            // Transform JSX to React.createElement calls
-            module = module.fold_with(&mut react(
-                self.source_map.clone(),
-                None,
-                ReactOptions {
-                    pragma: Some("React.createElement".to_string()),
-                    pragma_frag: Some("React.Fragment".to_string()),
-                    throw_if_namespace: false,
-                    development: false,
-                    use_builtins: false,
-                    use_spread: false,
-                    refresh: None,
-                    runtime: None,
-                    import_source: None,
-                    next: false,
-                },
-                top_level_mark,
-                unresolved_mark,
-            ));
-            
-            Ok(module)
-        })?
+            transform_module(module, &self.source_map, &TransformSettings::default())
+        })
     }
-    
-    pub fn generate_code(&self, module: &Module) -> Result<String> {
+
+    /// Generates `module`'s JS, plus its source map when `self.sourcemap` is
+    /// set — `None` otherwise, or when `self.inline_sourcemap` embedded it as
+    /// a `//# sourceMappingURL=data:...` comment on the code instead.
+    pub fn generate_code(&self, module: &Module) -> Result<(String, Option<String>)> {
+        let minified;
+        let module = if self.minify {
+            minified = self.minify_module(module.clone());
+            &minified
+        } else {
+            module
+        };
+
+        // The raw (generated pos, original pos) mappings only survive for
+        // the duration of the `JsWriter` borrow, so the source map is built
+        // from them before `buf`/`raw_mappings` go out of scope.
         let mut buf = Vec::new();
+        let mut raw_mappings = Vec::new();
         {
-            let writer = JsWriter::new(self.source_map.clone(), "\n", &mut buf, None);
+            let writer = if self.sourcemap {
+                JsWriter::new(self.source_map.clone(), "\n", &mut buf, Some(&mut raw_mappings))
+            } else {
+                JsWriter::new(self.source_map.clone(), "\n", &mut buf, None)
+            };
             let mut emitter = Emitter {
-                cfg: swc_ecma_codegen::Config::default(),
+                cfg: swc_ecma_codegen::Config::default()
+                    .with_minify(self.minify)
+                    .with_omit_trailing_semi(self.minify),
                 cm: self.source_map.clone(),
                 comments: None,
                 wr: writer,
             };
-            
-            emitter.emit_module(module)?;
+
+            // Unlike a parse failure, codegen has no source span left to
+            // resolve (it's writing an already-built AST) — the structured
+            // `DiagnosticItem` here just keeps codegen failures flowing
+            // through the same `SSRError::CodegenError` shape as a parse
+            // failure instead of a bare `{:?}`-formatted `io::Error`.
+            if let Err(e) = emitter.emit_module(module) {
+                return Err(
+                    SSRError::CodegenError(vec![DiagnosticItem::without_location(format!(
+                        "{:?}",
+                        e
+                    ))])
+                    .into(),
+                );
+            }
         }
-        
-        String::from_utf8(buf).context("Generated code is not valid UTF-8")
+
+        let mut code = String::from_utf8(buf).context("Generated code is not valid UTF-8")?;
+
+        if !self.sourcemap {
+            return Ok((code, None));
+        }
+
+        let source_map = self.source_map.build_source_map(&raw_mappings);
+        let mut map_buf = Vec::new();
+        source_map
+            .to_writer(&mut map_buf)
+            .context("Failed to serialize source map")?;
+        let map_json = String::from_utf8(map_buf).context("Source map is not valid UTF-8")?;
+
+        if self.inline_sourcemap {
+            code.push_str(&format!(
+                "\n//# sourceMappingURL=data:application/json;base64,{}\n",
+                base64::encode(&map_json)
+            ));
+            Ok((code, None))
+        } else {
+            Ok((code, Some(map_json)))
+        }
+    }
+
+    /// Run `swc_ecma_minifier`'s compress + mangle pass over `module` before
+    /// the minified codegen `generate_code` does when `self.minify` is set.
+    fn minify_module(&self, module: Module) -> Module {
+        optimize(
+            module,
+            self.source_map.clone(),
+            None,
+            None,
+            &MinifyOptions {
+                compress: Some(Default::default()),
+                mangle: Some(Default::default()),
+                ..Default::default()
+            },
+            &ExtraOptions {
+                unresolved_mark: swc_common::Mark::new(),
+                top_level_mark: swc_common::Mark::new(),
+            },
+        )
+    }
+}
+
+/// Minify `combine_html_and_script`'s output for the production build
+/// profile: drop HTML comments and collapse runs of whitespace between
+/// tags, while leaving `<pre>`/`<script>`/`<style>`/`<textarea>` bodies
+/// untouched — the inlined `<script>` is already minified JS by the time it
+/// reaches here (`js` came from a `self.minify`-enabled `generate_code`),
+/// and collapsing its whitespace blind could change string literals.
+fn minify_html_document(html: &str) -> String {
+    let without_comments = Regex::new(r"<!--[\s\S]*?-->")
+        .expect("valid regex")
+        .replace_all(html, "")
+        .to_string();
+
+    let preserved = Regex::new(r"(?is)<(pre|script|style|textarea)\b[^>]*>.*?</\1\s*>")
+        .expect("valid regex");
+
+    let mut out = String::with_capacity(without_comments.len());
+    let mut last_end = 0;
+    for m in preserved.find_iter(&without_comments) {
+        out.push_str(&collapse_whitespace(&without_comments[last_end..m.start()]));
+        out.push_str(m.as_str());
+        last_end = m.end();
     }
-}
\ No newline at end of file
+    out.push_str(&collapse_whitespace(&without_comments[last_end..]));
+    out
+}
+
+/// Collapse runs of whitespace to a single space, and drop whitespace that
+/// sits entirely between two tags (`>   <` -> `><`).
+fn collapse_whitespace(segment: &str) -> String {
+    let between_tags = Regex::new(r">\s+<").expect("valid regex").replace_all(segment, "><").to_string();
+    Regex::new(r"[ \t\r\n]+")
+        .expect("valid regex")
+        .replace_all(&between_tags, " ")
+        .trim()
+        .to_string()
+}
+
+/// Import specifiers scraped from `code` via regex (every `from "..."`),
+/// used only as a cache-invalidation signal (see `Compiler::cache_key_for`)
+/// — not a real parse, so it misses side-effect-only `import "..."`
+/// statements and dynamic `import()` calls, and a file reached only through
+/// one of those is left out of the cache key.
+fn static_import_specifiers(code: &str) -> Vec<String> {
+    Regex::new(r#"from\s*["']([^"']+)["']"#)
+        .expect("valid regex")
+        .captures_iter(code)
+        .map(|caps| caps[1].to_string())
+        .collect()
+}
+
+/// Parse TSX/JSX source into an AST against `source_map`, under `file_name`,
+/// lexing against `target` (a tsconfig's `compilerOptions.target`, or
+/// `EsVersion::Es2022` for standalone callers with no tsconfig in scope).
+/// Shared by `Compiler::parse_tsx` and `bundler::AppLoader`'s `swc_bundler`
+/// `Load` impl, which needs the parsed `SourceFile` alongside the `Module`
+/// so the bundler's spans resolve back to it.
+pub(crate) fn parse_module_with_filename(
+    source_map: &Lrc<SourceMap>,
+    code: &str,
+    file_name: swc_common::FileName,
+    target: EsVersion,
+) -> Result<(Lrc<swc_common::SourceFile>, Module)> {
+    let source_file = source_map.new_source_file(Lrc::new(file_name), code.to_string());
+
+    let lexer = Lexer::new(
+        Syntax::Typescript(TsConfig {
+            tsx: true,
+            decorators: false,
+            dts: false,
+            no_early_errors: true,
+            disallow_ambiguous_jsx_like: false,
+        }),
+        target,
+        StringInput::from(&*source_file),
+        None,
+    );
+
+    // A plain `{:?}`-formatted parser error has no file/line/col — every
+    // real module goes through this function (`AppLoader::load`), so a
+    // typo'd entry anywhere in the graph used to come back as an opaque
+    // blob with no indication of where to look. `CollectingEmitter`
+    // resolves the failing span back to `source_map` instead.
+    let collector = CollectingEmitter::new(source_map.clone());
+    let handler = Handler::with_emitter(true, false, Box::new(collector.clone()));
+
+    let mut parser = Parser::new_from(lexer);
+    let module = match parser.parse_module() {
+        Ok(module) => module,
+        Err(e) => {
+            e.into_diagnostic(&handler).emit();
+            return Err(SSRError::ParseError(collector.take_diagnostics()).into());
+        }
+    };
+
+    Ok((source_file, module))
+}
+
+/// Where a module's source came from on disk, used to pick lexer settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaType {
+    TypeScript,
+    Tsx,
+    JavaScript,
+    Jsx,
+}
+
+impl MediaType {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ts") => MediaType::TypeScript,
+            Some("tsx") => MediaType::Tsx,
+            Some("jsx") => MediaType::Jsx,
+            _ => MediaType::JavaScript,
+        }
+    }
+
+    fn is_jsx_like(self) -> bool {
+        matches!(self, MediaType::Tsx | MediaType::Jsx)
+    }
+}
+
+/// Browser-target transpilation settings resolved once per CLI invocation
+/// from `--targets`/`--corejs`/`--preset-env-entry` and threaded into every
+/// module `AppLoader` loads via `transform_module`.
+#[derive(Debug, Clone)]
+pub struct TransformSettings {
+    /// Browserslist-style query (e.g. `"defaults"`, `"> 0.5%"`) to transpile
+    /// and polyfill for. `None` skips the `preset_env` pass entirely and
+    /// leaves already-modern output alone.
+    pub targets: Option<String>,
+    /// `core-js` major version used for injected polyfill imports.
+    pub corejs: f64,
+    /// `Usage` scans the AST and injects only the polyfills actually used;
+    /// `Entry` replaces an explicit `import "core-js"` with the granular
+    /// imports for `targets`.
+    pub preset_env_mode: PresetEnvMode,
+    /// JSX runtime, pragma, and parser/lexer target resolved from the
+    /// nearest `tsconfig.json` above the entry file, if any — see
+    /// `TransformSettings::with_tsconfig_near`.
+    pub tsconfig: TsConfigResolved,
+}
+
+impl Default for TransformSettings {
+    fn default() -> Self {
+        Self {
+            targets: None,
+            corejs: 3.0,
+            preset_env_mode: PresetEnvMode::Usage,
+            tsconfig: TsConfigResolved::default(),
+        }
+    }
+}
+
+impl TransformSettings {
+    pub fn from_args(args: &crate::cli::Args) -> Self {
+        Self {
+            targets: args.targets.clone(),
+            corejs: args.corejs,
+            preset_env_mode: if args.preset_env_entry {
+                PresetEnvMode::Entry
+            } else {
+                PresetEnvMode::Usage
+            },
+            tsconfig: TsConfigResolved::default(),
+        }
+    }
+
+    /// Walks up from `start_dir` for the nearest `tsconfig.json` and, if one
+    /// is found, overrides `self.tsconfig`'s classic-runtime/ES2020 defaults
+    /// with what it resolves to. Left at the default when none is found (or
+    /// it fails to parse).
+    pub fn with_tsconfig_near(mut self, start_dir: &Path) -> Self {
+        if let Ok(Some(resolved)) = crate::tsconfig::load_tsconfig(start_dir) {
+            self.tsconfig = resolved;
+        }
+        self
+    }
+}
+
+/// Strips TypeScript types and transforms JSX to `React.createElement`
+/// calls, optionally running `preset_env` beforehand when `settings.targets`
+/// is set. Shared by `Compiler::transform_tsx` (its own self-contained
+/// `GLOBALS` scope) and `bundler::AppLoader::load` (which runs inside
+/// `Bundler::link`'s `GLOBALS` scope already, so it calls this directly) —
+/// callers MUST invoke this from within a `GLOBALS::set` scope, since
+/// `Mark::new()` panics outside one.
+pub(crate) fn transform_module(
+    module: Module,
+    source_map: &Lrc<SourceMap>,
+    settings: &TransformSettings,
+) -> Result<Module> {
+    let unresolved_mark = Mark::new();
+    let top_level_mark = Mark::new();
+
+    let mut module = module.fold_with(&mut resolver(unresolved_mark, top_level_mark, true));
+    module = module.fold_with(&mut strip(top_level_mark));
+
+    if let Some(targets) = &settings.targets {
+        module = module.fold_with(&mut preset_env(
+            unresolved_mark,
+            None,
+            PresetEnvConfig {
+                targets: Some(PresetEnvTargets::Query(PresetEnvQuery::Single(targets.clone()))),
+                mode: Some(settings.preset_env_mode),
+                core_js: Some(settings.corejs),
+                ..Default::default()
+            },
+            Default::default(),
+        ));
+    }
+
+    module = module.fold_with(&mut react(
+        source_map.clone(),
+        None,
+        ReactOptions {
+            pragma: settings.tsconfig.pragma.clone(),
+            pragma_frag: settings.tsconfig.pragma_frag.clone(),
+            throw_if_namespace: false,
+            development: false,
+            use_builtins: false,
+            use_spread: false,
+            refresh: None,
+            runtime: Some(settings.tsconfig.runtime),
+            import_source: settings.tsconfig.import_source.clone(),
+            next: false,
+        },
+        top_level_mark,
+        unresolved_mark,
+    ));
+
+    Ok(module)
+}
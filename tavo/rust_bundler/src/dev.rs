@@ -0,0 +1,340 @@
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, Mutex};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::compiler::{Compiler, TransformSettings};
+use crate::fs_router::resolve_route;
+
+/// One rebuilt route, pushed to every connected browser after a relevant
+/// filesystem event. The client-side listener re-renders `#root` and
+/// re-evaluates `js` the same way a one-shot `--ssr --hydrate` build would.
+#[derive(Serialize, Clone)]
+pub struct RouteSnapshot {
+    pub route: String,
+    pub html: String,
+    pub js: String,
+}
+
+/// Starts the long-lived dev server. A single listener on `port` serves
+/// both roles a browser needs: a plain HTTP `GET` renders the requested
+/// route's initial HTML (with the client bootstrap script embedded, see
+/// [`client_bootstrap_script`]), while a WebSocket upgrade on the same port
+/// opens the live-reload channel that script connects to receive
+/// `RouteSnapshot`s on. A background watcher over `app_dir` rebuilds the
+/// affected route and broadcasts one on every `page.tsx`/`layout.tsx`
+/// change. Analogous to a `getStream` entrypoint with watching already
+/// wired in, as opposed to the one-shot `get` the rest of the CLI performs.
+pub async fn run(app_dir: PathBuf, port: u16, transform_settings: TransformSettings) -> Result<()> {
+    let (tx, _rx) = broadcast::channel::<RouteSnapshot>(16);
+    let compiler = Arc::new(Mutex::new(Compiler::with_transform_settings(transform_settings)?));
+
+    let watcher_tx = tx.clone();
+    let watcher_app_dir = app_dir.clone();
+    let watcher_compiler = compiler.clone();
+    tokio::spawn(async move {
+        if let Err(err) = watch_and_rebuild(watcher_app_dir, watcher_tx, watcher_compiler).await {
+            eprintln!("tavo dev: watcher stopped: {:#}", err);
+        }
+    });
+
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .with_context(|| format!("Failed to bind dev server to port {}", port))?;
+    eprintln!("tavo dev listening on http://127.0.0.1:{}", port);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(handle_connection(
+            stream,
+            compiler.clone(),
+            app_dir.clone(),
+            tx.subscribe(),
+            port,
+        ));
+    }
+}
+
+/// Every accepted connection is either a plain HTTP page request or a
+/// WebSocket upgrade for the live-reload channel — peeking the first bytes
+/// off the socket (without consuming them, so the real handler still sees a
+/// full, untouched request) is enough to tell which, since an upgrade
+/// request always carries an `Upgrade: websocket` header in its first
+/// segment.
+async fn handle_connection(
+    stream: TcpStream,
+    compiler: Arc<Mutex<Compiler>>,
+    app_dir: PathBuf,
+    updates: broadcast::Receiver<RouteSnapshot>,
+    port: u16,
+) {
+    let mut peek_buf = [0u8; 1024];
+    let peeked = match stream.peek(&mut peek_buf).await {
+        Ok(n) => n,
+        Err(_) => return,
+    };
+    let is_websocket_upgrade = peek_buf[..peeked]
+        .to_ascii_lowercase()
+        .windows(b"upgrade: websocket".len())
+        .any(|window| window == b"upgrade: websocket");
+
+    if is_websocket_upgrade {
+        handle_client(stream, updates).await;
+    } else {
+        handle_http_request(stream, compiler, app_dir, port).await;
+    }
+}
+
+async fn handle_client(stream: TcpStream, mut updates: broadcast::Receiver<RouteSnapshot>) {
+    let ws_stream = match tokio_tungstenite::accept_async(stream).await {
+        Ok(ws) => ws,
+        Err(err) => {
+            eprintln!("tavo dev: client handshake failed: {}", err);
+            return;
+        }
+    };
+    let (mut write, _read) = ws_stream.split();
+
+    while let Ok(snapshot) = updates.recv().await {
+        let Ok(payload) = serde_json::to_string(&snapshot) else {
+            continue;
+        };
+        if write.send(Message::Text(payload)).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Renders the route a browser's initial `GET` asked for and serves it back
+/// as a complete HTML document, the same SSR+hydrate document a one-shot
+/// `--ssr --hydrate` build would produce, with [`client_bootstrap_script`]
+/// appended so the page immediately opens the live-reload WebSocket.
+async fn handle_http_request(
+    mut stream: TcpStream,
+    compiler: Arc<Mutex<Compiler>>,
+    app_dir: PathBuf,
+    port: u16,
+) {
+    let route = match read_request_route(&mut stream).await {
+        Ok(route) => route,
+        Err(err) => {
+            eprintln!("tavo dev: failed to read request: {:#}", err);
+            return;
+        }
+    };
+
+    let app_dir_str = app_dir.to_string_lossy().to_string();
+    let page = {
+        let mut compiler = compiler.lock().await;
+        render_initial_page(&mut compiler, &app_dir_str, &route, port).await
+    };
+
+    let response = match page {
+        Ok(html) => http_response(200, "OK", "text/html; charset=utf-8", &html),
+        Err(err) => http_response(
+            500,
+            "Internal Server Error",
+            "text/plain; charset=utf-8",
+            &format!("Failed to render {}: {:#}", route, err),
+        ),
+    };
+
+    let _ = stream.write_all(response.as_bytes()).await;
+}
+
+/// Reads just enough of a raw HTTP/1.1 request to pull the request-line's
+/// path out of it — there's no router here beyond `resolve_route`, so
+/// nothing else in the request (headers, body) is needed.
+async fn read_request_route(stream: &mut TcpStream) -> Result<String> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 512];
+    loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if buf.windows(4).any(|w| w == b"\r\n\r\n") || buf.len() > 8192 {
+            break;
+        }
+    }
+
+    let request = String::from_utf8_lossy(&buf);
+    let path = request
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or("/");
+    let path = path.split('?').next().unwrap_or("/");
+    Ok(if path.is_empty() { "/".to_string() } else { path.to_string() })
+}
+
+fn http_response(status: u16, reason: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+async fn render_initial_page(
+    compiler: &mut Compiler,
+    app_dir: &str,
+    route: &str,
+    port: u16,
+) -> Result<String> {
+    let route_info = resolve_route(route, app_dir)?;
+    let (html, _assets) = compiler.render_ssr(&route_info.file_path).await?;
+    let hydration = compiler.generate_hydration_script(&route_info.file_path).await?;
+    let js = format!("{}\n{}", hydration.entry, client_bootstrap_script(port));
+    compiler.combine_html_and_script(&html, &js)
+}
+
+/// Opens the live-reload WebSocket and, on every `RouteSnapshot` meant for
+/// the page currently loaded, replaces `#root`'s contents and re-runs the
+/// freshly generated hydration script — the client half of `watch_and_rebuild`
+/// broadcasting a rebuild.
+fn client_bootstrap_script(port: u16) -> String {
+    format!(
+        r#"(function () {{
+  if (typeof window === 'undefined') {{ return; }}
+  var ws = new WebSocket('ws://' + location.hostname + ':{port}');
+  ws.onmessage = function (event) {{
+    var snapshot;
+    try {{
+      snapshot = JSON.parse(event.data);
+    }} catch (err) {{
+      return;
+    }}
+    if (snapshot.route !== location.pathname) {{ return; }}
+    var root = document.getElementById('root');
+    if (root) {{ root.innerHTML = snapshot.html; }}
+    try {{
+      (0, eval)(snapshot.js);
+    }} catch (err) {{
+      console.error('tavo dev: failed to apply update', err);
+    }}
+  }};
+}})();"#,
+        port = port
+    )
+}
+
+/// Watches `app_dir` and, for each event that touches a `page.tsx`/
+/// `layout.tsx`, recomputes just the affected route via `resolve_route` and
+/// the existing SSR/hydration pipeline, then broadcasts the result.
+/// Unrelated events (editor swap files, non-route sources) are ignored
+/// rather than triggering a full rebuild of every route.
+async fn watch_and_rebuild(
+    app_dir: PathBuf,
+    tx: broadcast::Sender<RouteSnapshot>,
+    compiler: Arc<Mutex<Compiler>>,
+) -> Result<()> {
+    let (fs_tx, mut fs_rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let mut watcher: RecommendedWatcher =
+        notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                let _ = fs_tx.send(event);
+            }
+        })?;
+    watcher.watch(&app_dir, RecursiveMode::Recursive)?;
+
+    let mut known_routes = HashSet::new();
+
+    while let Some(event) = fs_rx.recv().await {
+        let Some(route) = affected_route(&event, &app_dir) else {
+            continue;
+        };
+        known_routes.insert(route.clone());
+
+        let result = {
+            let mut compiler = compiler.lock().await;
+            rebuild_route(&mut compiler, &app_dir, &route).await
+        };
+
+        match result {
+            Ok(snapshot) => {
+                // No error if there are no subscribers yet: the watcher
+                // keeps running regardless, so the next browser to connect
+                // gets the next rebuild instead of a replay of this one.
+                let _ = tx.send(snapshot);
+            }
+            Err(err) => eprintln!("tavo dev: rebuild failed for {}: {:#}", route, err),
+        }
+    }
+
+    Ok(())
+}
+
+/// Maps a raw filesystem event to the route it affects, or `None` when the
+/// event isn't a `page.tsx`/`layout.tsx` change.
+fn affected_route(event: &notify::Event, app_dir: &Path) -> Option<String> {
+    let path = event.paths.first()?;
+    let name = path.file_name()?.to_str()?;
+    if name != "page.tsx" && name != "layout.tsx" {
+        return None;
+    }
+
+    let page_file = if name == "page.tsx" {
+        path.clone()
+    } else {
+        // A layout change affects every route beneath it; rebuilding the
+        // nearest page.tsx in the same directory is a reasonable proxy
+        // until sub-route invalidation is tracked more precisely.
+        path.parent()?.join("page.tsx")
+    };
+
+    if !page_file.exists() {
+        return None;
+    }
+
+    let relative = page_file.strip_prefix(app_dir).ok()?;
+    Some(file_path_to_route(relative))
+}
+
+fn file_path_to_route(relative: &Path) -> String {
+    let mut components: Vec<_> = relative.components().collect();
+    if components.last().and_then(|c| c.as_os_str().to_str()) == Some("page.tsx") {
+        components.pop();
+    }
+    if components.is_empty() {
+        return "/".to_string();
+    }
+    format!(
+        "/{}",
+        components
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    )
+}
+
+async fn rebuild_route(
+    compiler: &mut Compiler,
+    app_dir: &Path,
+    route: &str,
+) -> Result<RouteSnapshot> {
+    let app_dir_str = app_dir.to_string_lossy().to_string();
+    let route_info = resolve_route(route, &app_dir_str)?;
+
+    let (html, _assets) = compiler.render_ssr(&route_info.file_path).await?;
+    let hydration = compiler.generate_hydration_script(&route_info.file_path).await?;
+
+    Ok(RouteSnapshot {
+        route: route.to_string(),
+        html,
+        js: hydration.entry,
+    })
+}
@@ -1,5 +1,135 @@
 use std::fmt;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use serde::Serialize;
+use swc_common::errors::{Diagnostic, Emitter};
+use swc_common::sync::Lrc;
+use swc_common::SourceMap;
+
+/// A single parse/codegen diagnostic with its source location resolved,
+/// mirroring Deno's `Diagnostic`/`DiagnosticItem` pair so JSON output can
+/// surface actionable `{message, file, line, col, snippet}` records instead
+/// of a raw `{:?}`-formatted error.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticItem {
+    pub message: String,
+    pub file: String,
+    pub line: usize,
+    pub col: usize,
+    pub snippet: String,
+}
+
+impl DiagnosticItem {
+    /// A diagnostic with no resolvable source span, for call sites that
+    /// don't originate from an SWC `Handler` (e.g. our own graph checks).
+    pub fn without_location<S: Into<String>>(message: S) -> Self {
+        Self {
+            message: message.into(),
+            file: String::new(),
+            line: 0,
+            col: 0,
+            snippet: String::new(),
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.file.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}:{}:{}: {}", self.file, self.line, self.col, self.message)?;
+            if !self.snippet.is_empty() {
+                write!(f, "\n    {}", self.snippet)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// An `swc_common::errors::Emitter` that buffers diagnostics into a `Vec`
+/// instead of writing them straight to stderr, resolving each diagnostic's
+/// primary span to `(file, line, column)` plus a snippet of the offending
+/// line via `SourceMap::lookup_char_pos`.
+#[derive(Clone)]
+pub struct CollectingEmitter {
+    source_map: Lrc<SourceMap>,
+    diagnostics: Arc<Mutex<Vec<DiagnosticItem>>>,
+}
+
+impl CollectingEmitter {
+    pub fn new(source_map: Lrc<SourceMap>) -> Self {
+        Self {
+            source_map,
+            diagnostics: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Drain every diagnostic collected so far.
+    pub fn take_diagnostics(&self) -> Vec<DiagnosticItem> {
+        std::mem::take(&mut *self.diagnostics.lock().expect("diagnostics lock poisoned"))
+    }
+}
+
+impl Emitter for CollectingEmitter {
+    fn emit(&mut self, db: &mut swc_common::errors::DiagnosticBuilder<'_>) {
+        let message = db.message();
+        let location = db
+            .span
+            .primary_span()
+            .map(|span| self.source_map.lookup_char_pos(span.lo()));
+
+        let item = match location {
+            Some(loc) => DiagnosticItem {
+                message,
+                file: loc.file.name.to_string(),
+                line: loc.line,
+                col: loc.col_display + 1,
+                snippet: self
+                    .source_map
+                    .span_to_snippet(db.span.primary_span().unwrap())
+                    .unwrap_or_default(),
+            },
+            None => DiagnosticItem::without_location(message),
+        };
+
+        self.diagnostics
+            .lock()
+            .expect("diagnostics lock poisoned")
+            .push(item);
+    }
+}
+
+/// Carries every diagnostic from a failed transpile pass (parse or codegen)
+/// so JSON output can surface them as a structured `errors` array instead of
+/// a single `{:?}`-formatted blob. Implements `std::error::Error` so it flows
+/// through `anyhow::Result`.
+#[derive(Debug)]
+pub struct TranspileError {
+    pub diagnostics: Vec<DiagnosticItem>,
+}
+
+impl TranspileError {
+    pub fn new(diagnostics: Vec<DiagnosticItem>) -> Self {
+        Self { diagnostics }
+    }
+}
+
+impl fmt::Display for TranspileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Transpile failed with {} diagnostic(s):",
+            self.diagnostics.len()
+        )?;
+        for diagnostic in &self.diagnostics {
+            write!(f, "\n  {}", diagnostic)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for TranspileError {}
 
 #[derive(Debug)]
 pub enum SSRError {
@@ -7,10 +137,12 @@ pub enum SSRError {
     FileRead(PathBuf, std::io::Error),
     FileNotFound(PathBuf),
     InvalidPath(String),
-    
-    // Compilation errors
-    ParseError(String),
-    CodegenError(String),
+
+    // Compilation errors: each diagnostic carries its own resolved location,
+    // so the JSON output mode can surface a structured `errors` array
+    // instead of a single `{:?}`-formatted blob.
+    ParseError(Vec<DiagnosticItem>),
+    CodegenError(Vec<DiagnosticItem>),
     
     // Runtime errors
     JsRuntime(String),
@@ -39,11 +171,19 @@ impl fmt::Display for SSRError {
             SSRError::InvalidPath(msg) => {
                 write!(f, "Invalid path: {}", msg)
             }
-            SSRError::ParseError(msg) => {
-                write!(f, "Parse error: {}", msg)
+            SSRError::ParseError(diagnostics) => {
+                write!(f, "Parse error:")?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n  {}", diagnostic)?;
+                }
+                Ok(())
             }
-            SSRError::CodegenError(msg) => {
-                write!(f, "Code generation error: {}", msg)
+            SSRError::CodegenError(diagnostics) => {
+                write!(f, "Code generation error:")?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n  {}", diagnostic)?;
+                }
+                Ok(())
             }
             SSRError::JsRuntime(msg) => {
                 write!(f, "JavaScript runtime error: {}", msg)
@@ -95,7 +235,10 @@ impl From<serde_json::Error> for SSRError {
 
 impl From<regex::Error> for SSRError {
     fn from(err: regex::Error) -> Self {
-        SSRError::ParseError(format!("Regex error: {}", err))
+        SSRError::ParseError(vec![DiagnosticItem::without_location(format!(
+            "Regex error: {}",
+            err
+        ))])
     }
 }
 
@@ -104,11 +247,20 @@ impl SSRError {
     pub fn file_not_found<P: Into<PathBuf>>(path: P) -> Self {
         SSRError::FileNotFound(path.into())
     }
-    
+
+    /// Wrap a single ad-hoc message as a parse error with no resolvable
+    /// source location. Prefer [`SSRError::ParseError`] directly when real
+    /// [`DiagnosticItem`]s from a [`CollectingEmitter`] are available.
     pub fn parse_error<S: Into<String>>(msg: S) -> Self {
-        SSRError::ParseError(msg.into())
+        SSRError::ParseError(vec![DiagnosticItem::without_location(msg)])
     }
-    
+
+    /// Wrap a single ad-hoc message as a codegen error with no resolvable
+    /// source location.
+    pub fn codegen_error<S: Into<String>>(msg: S) -> Self {
+        SSRError::CodegenError(vec![DiagnosticItem::without_location(msg)])
+    }
+
     pub fn js_runtime_error<S: Into<String>>(msg: S) -> Self {
         SSRError::JsRuntime(msg.into())
     }
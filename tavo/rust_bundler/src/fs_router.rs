@@ -5,11 +5,53 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+/// A route param as captured from a file-based segment: a plain `[name]`
+/// segment yields `Single`, while a `[...name]`/`[[...name]]` catch-all
+/// yields `Multi` with one entry per captured path segment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RouteParam {
+    Single(String),
+    Multi(Vec<String>),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouteInfo {
     pub file_path: PathBuf,
-    pub params: HashMap<String, String>,
+    pub params: HashMap<String, RouteParam>,
     pub layout_chain: Vec<PathBuf>,
+    /// `@slot` parallel-route folders matched alongside this route, keyed
+    /// by slot name (the folder name without the leading `@`).
+    pub slots: HashMap<String, PathBuf>,
+    /// The route's query string (if any), parsed on demand via
+    /// `SSRContext::query`. Split off before path-matching, so a `?`
+    /// doesn't stop `::param`/`:::param` segments from matching the path
+    /// that precedes it.
+    pub query: SSRContext,
+}
+
+/// A route's query string, deserialized on demand into any `Deserialize`
+/// type via `serde_qs` (e.g. `?sort=asc&page=2` into
+/// `struct Query { sort: String, page: u32 }`). Construction never fails —
+/// an unparseable query string is stored as-is, same as an empty one, since
+/// a malformed query shouldn't stop the route itself from resolving; only
+/// `query::<T>()` surfaces a deserialization error, and only to the caller
+/// that actually asked for a typed value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SSRContext {
+    raw: String,
+}
+
+impl SSRContext {
+    fn new(raw: &str) -> Self {
+        Self { raw: raw.to_string() }
+    }
+
+    /// Deserializes the query string into `T`. An empty query string
+    /// deserializes into whatever `T` does with every field absent —
+    /// typically only valid when every field is `Option`/has a default.
+    pub fn query<T: serde::de::DeserializeOwned>(&self) -> Result<T> {
+        serde_qs::from_str(&self.raw).map_err(|e| anyhow!("Failed to parse query string: {}", e))
+    }
 }
 
 pub fn resolve_route(route: &str, app_dir: &str) -> Result<RouteInfo> {
@@ -18,12 +60,14 @@ pub fn resolve_route(route: &str, app_dir: &str) -> Result<RouteInfo> {
         return Err(anyhow!("App directory does not exist: {}", app_dir));
     }
 
+    let (path, query) = split_query(route);
+
     // Find all page.tsx files
     let page_files = find_page_files(app_path)?;
-    
+
     // Try to match the route
     for page_file in page_files {
-        if let Some(route_info) = try_match_route(route, &page_file, app_path)? {
+        if let Some(route_info) = try_match_route(path, query, &page_file, app_path)? {
             return Ok(route_info);
         }
     }
@@ -31,168 +75,371 @@ pub fn resolve_route(route: &str, app_dir: &str) -> Result<RouteInfo> {
     Err(anyhow!("No matching page for route: {}", route))
 }
 
+/// Splits `route` into its path and query string (without the leading
+/// `?`), so the router's path-matching regexes never see the `?` or
+/// anything after it. No query string yields an empty one, same as
+/// `SSRContext::query`'s "absent == empty" treatment.
+fn split_query(route: &str) -> (&str, &str) {
+    match route.split_once('?') {
+        Some((path, query)) => (path, query),
+        None => (route, ""),
+    }
+}
+
+/// Walks `app_dir` for `page.tsx` files, skipping ones that live inside a
+/// `@slot` parallel-route folder — those are matched as slots of their
+/// parent route in `try_match_route` rather than as routes of their own.
 fn find_page_files(app_dir: &Path) -> Result<Vec<PathBuf>> {
     let mut page_files = Vec::new();
-    
+
     for entry in WalkDir::new(app_dir) {
         let entry = entry?;
         let path = entry.path();
-        
-        if path.file_name().and_then(|s| s.to_str()) == Some("page.tsx") {
+
+        if path.file_name().and_then(|s| s.to_str()) == Some("page.tsx")
+            && !is_parallel_slot_page(path, app_dir)
+        {
             page_files.push(path.to_path_buf());
         }
     }
-    
+
     Ok(page_files)
 }
 
-fn try_match_route(route: &str, page_file: &Path, app_dir: &Path) -> Result<Option<RouteInfo>> {
+fn is_parallel_slot_page(page_file: &Path, app_dir: &Path) -> bool {
+    page_file
+        .strip_prefix(app_dir)
+        .ok()
+        .and_then(|relative| relative.parent())
+        .map(|parent| {
+            parent.components().any(|c| {
+                c.as_os_str()
+                    .to_str()
+                    .map(|s| s.starts_with('@'))
+                    .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn try_match_route(
+    route: &str,
+    query: &str,
+    page_file: &Path,
+    app_dir: &Path,
+) -> Result<Option<RouteInfo>> {
     // Convert file path to route pattern
     let relative_path = page_file.strip_prefix(app_dir)?;
     let route_pattern = file_path_to_route_pattern(relative_path)?;
-    
+
     // Try to match route against pattern
     if let Some(params) = match_route_pattern(&route_pattern, route)? {
-        // Find layout chain
         let layout_chain = find_layout_chain(page_file, app_dir)?;
-        
+        let slots = find_parallel_slots(page_file)?;
+
         let route_info = RouteInfo {
             file_path: page_file.to_path_buf(),
             params,
             layout_chain,
+            slots,
+            query: SSRContext::new(query),
         };
-        
+
         return Ok(Some(route_info));
     }
-    
+
     Ok(None)
 }
 
+/// Converts a `page.tsx`'s path into a route pattern. `(group)` folders
+/// organize files on disk without contributing a URL segment and are
+/// dropped entirely; the remaining segments are rewritten:
+/// - `[param]` -> a single-value capture (`::param`)
+/// - `[...param]` -> a catch-all capture, one or more trailing segments
+///   (`:::param`)
+/// - `[[...param]]` -> an optional catch-all, zero or more trailing
+///   segments (`:::?:param`)
 fn file_path_to_route_pattern(relative_path: &Path) -> Result<String> {
     let mut pattern = String::new();
     let mut components = relative_path.components().collect::<Vec<_>>();
-    
+
     // Remove page.tsx from the end
     if components.last().and_then(|c| c.as_os_str().to_str()) == Some("page.tsx") {
         components.pop();
     }
-    
+
     for component in components {
         let component_str = component.as_os_str().to_str()
             .ok_or_else(|| anyhow!("Invalid UTF-8 in path component"))?;
-            
+
+        // Route groups organize files on disk without affecting the URL.
+        if component_str.starts_with('(') && component_str.ends_with(')') {
+            continue;
+        }
+
         pattern.push('/');
-        
-        // Handle dynamic segments [param]
-        if component_str.starts_with('[') && component_str.ends_with(']') {
+
+        if let Some(param_name) = component_str
+            .strip_prefix("[[...")
+            .and_then(|s| s.strip_suffix("]]"))
+        {
+            pattern.push_str(&format!(":::?:{}", param_name));
+        } else if let Some(param_name) = component_str
+            .strip_prefix("[...")
+            .and_then(|s| s.strip_suffix(']'))
+        {
+            pattern.push_str(&format!(":::{}", param_name));
+        } else if component_str.starts_with('[') && component_str.ends_with(']') {
             let param_name = &component_str[1..component_str.len()-1];
             pattern.push_str(&format!("::{}", param_name));
         } else {
             pattern.push_str(component_str);
         }
     }
-    
+
     if pattern.is_empty() {
         pattern = "/".to_string();
     }
-    
+
     Ok(pattern)
 }
 
-fn match_route_pattern(pattern: &str, route: &str) -> Result<Option<HashMap<String, String>>> {
-    let mut params = HashMap::new();
-    
-    // Convert pattern to regex
-    let regex_pattern = pattern
-        .split('/')
-        .map(|segment| {
-            if segment.starts_with("::") {
-                "([^/]+)".to_string()
-            } else {
-                regex::escape(segment)
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("/");
-    
-    let regex_pattern = format!("^{}$", regex_pattern);
+enum ParamKind {
+    Single,
+    Multi,
+}
+
+fn match_route_pattern(pattern: &str, route: &str) -> Result<Option<HashMap<String, RouteParam>>> {
+    if pattern == "/" {
+        return Ok(if route == "/" { Some(HashMap::new()) } else { None });
+    }
+
+    let segments: Vec<&str> = pattern.trim_start_matches('/').split('/').collect();
+
+    // A catch-all is only ever valid as the final segment of a pattern.
+    let (fixed_segments, catch_all) = match segments.split_last() {
+        Some((last, rest)) if last.starts_with(":::") => (rest, Some(*last)),
+        _ => (segments.as_slice(), None),
+    };
+
+    let mut regex_pattern = String::from("^");
+    let mut param_names: Vec<(String, ParamKind)> = Vec::new();
+
+    for segment in fixed_segments {
+        regex_pattern.push('/');
+        if let Some(name) = segment.strip_prefix("::") {
+            regex_pattern.push_str("([^/]+)");
+            param_names.push((name.to_string(), ParamKind::Single));
+        } else {
+            regex_pattern.push_str(&regex::escape(segment));
+        }
+    }
+
+    match catch_all {
+        Some(segment) if segment.starts_with(":::?:") => {
+            let name = &segment[":::?:".len()..];
+            // Optional catch-all: the separating slash and the capture are
+            // both optional, so it also matches the parent route exactly.
+            regex_pattern.push_str("(?:/(.+))?");
+            param_names.push((name.to_string(), ParamKind::Multi));
+        }
+        Some(segment) => {
+            let name = &segment[":::".len()..];
+            regex_pattern.push_str("/(.+)");
+            param_names.push((name.to_string(), ParamKind::Multi));
+        }
+        None => {}
+    }
+
+    regex_pattern.push('$');
     let regex = Regex::new(&regex_pattern)?;
-    
-    if let Some(captures) = regex.captures(route) {
-        let param_names: Vec<&str> = pattern
-            .split('/')
-            .filter_map(|segment| {
-                if segment.starts_with("::") {
-                    Some(&segment[2..])
-                } else {
-                    None
-                }
-            })
-            .collect();
-        
-        for (i, param_name) in param_names.iter().enumerate() {
-            if let Some(capture) = captures.get(i + 1) {
-                params.insert(param_name.to_string(), capture.as_str().to_string());
+
+    let captures = match regex.captures(route) {
+        Some(captures) => captures,
+        None => return Ok(None),
+    };
+
+    let mut params = HashMap::new();
+    for (i, (name, kind)) in param_names.iter().enumerate() {
+        let Some(raw) = captures.get(i + 1) else {
+            continue;
+        };
+        let value = match kind {
+            ParamKind::Single => RouteParam::Single(raw.as_str().to_string()),
+            ParamKind::Multi => {
+                RouteParam::Multi(raw.as_str().split('/').map(str::to_string).collect())
             }
-        }
-        
-        return Ok(Some(params));
+        };
+        params.insert(name.clone(), value);
     }
-    
-    Ok(None)
+
+    Ok(Some(params))
 }
 
 fn find_layout_chain(page_file: &Path, app_dir: &Path) -> Result<Vec<PathBuf>> {
     let mut layouts = Vec::new();
     let relative_path = page_file.strip_prefix(app_dir)?;
     let mut current_dir = app_dir.to_path_buf();
-    
+
     // Walk up the directory tree looking for layout files
     for component in relative_path.components() {
+        let component_str = component.as_os_str().to_str().unwrap_or_default();
         current_dir = current_dir.join(component);
-        
+
+        // `@slot` folders never appear in a matched page's own path (slot
+        // pages are excluded from routing in `find_page_files`), but skip
+        // them here too rather than relying on that invariant silently.
+        // `(group)` folders DO still contribute their own `layout.tsx`, so
+        // they aren't skipped.
+        if component_str.starts_with('@') {
+            continue;
+        }
+
         let layout_file = current_dir.join("layout.tsx");
         if layout_file.exists() && layout_file != *page_file {
             layouts.push(layout_file);
         }
     }
-    
+
     // Remove the page file itself if it was added
     layouts.retain(|p| p.file_name().and_then(|s| s.to_str()) != Some("page.tsx"));
-    
+
     Ok(layouts)
 }
 
+/// Scans `page_file`'s own route directory for `@slot` parallel-route
+/// folders and maps each one's `page.tsx` to its slot name (the folder
+/// name minus the leading `@`).
+fn find_parallel_slots(page_file: &Path) -> Result<HashMap<String, PathBuf>> {
+    let mut slots = HashMap::new();
+
+    let Some(route_dir) = page_file.parent() else {
+        return Ok(slots);
+    };
+
+    for entry in std::fs::read_dir(route_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let Some(dir_name) = path.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let Some(slot_name) = dir_name.strip_prefix('@') else {
+            continue;
+        };
+
+        let slot_page = path.join("page.tsx");
+        if slot_page.exists() {
+            slots.insert(slot_name.to_string(), slot_page);
+        }
+    }
+
+    Ok(slots)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::HashMap;
-    
+
     #[test]
     fn test_file_path_to_route_pattern() {
         assert_eq!(file_path_to_route_pattern(Path::new("page.tsx")).unwrap(), "/");
         assert_eq!(file_path_to_route_pattern(Path::new("about/page.tsx")).unwrap(), "/about");
         assert_eq!(file_path_to_route_pattern(Path::new("blog/[slug]/page.tsx")).unwrap(), "/blog/::slug");
+        assert_eq!(
+            file_path_to_route_pattern(Path::new("shop/[...slug]/page.tsx")).unwrap(),
+            "/shop/:::slug"
+        );
+        assert_eq!(
+            file_path_to_route_pattern(Path::new("docs/[[...slug]]/page.tsx")).unwrap(),
+            "/docs/:::?:slug"
+        );
+        assert_eq!(
+            file_path_to_route_pattern(Path::new("(marketing)/about/page.tsx")).unwrap(),
+            "/about"
+        );
     }
-    
+
     #[test]
     fn test_match_route_pattern() {
         let mut expected = HashMap::new();
-        expected.insert("slug".to_string(), "hello-world".to_string());
-        
+        expected.insert("slug".to_string(), RouteParam::Single("hello-world".to_string()));
+
         assert_eq!(
             match_route_pattern("/blog/::slug", "/blog/hello-world").unwrap(),
             Some(expected)
         );
-        
+
         assert_eq!(
             match_route_pattern("/about", "/about").unwrap(),
             Some(HashMap::new())
         );
-        
+
         assert_eq!(
             match_route_pattern("/blog/::slug", "/about").unwrap(),
             None
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_match_catch_all_pattern() {
+        let mut expected = HashMap::new();
+        expected.insert(
+            "slug".to_string(),
+            RouteParam::Multi(vec!["a".to_string(), "b".to_string()]),
+        );
+
+        assert_eq!(
+            match_route_pattern("/shop/:::slug", "/shop/a/b").unwrap(),
+            Some(expected)
+        );
+        assert_eq!(match_route_pattern("/shop/:::slug", "/shop").unwrap(), None);
+    }
+
+    #[test]
+    fn test_split_query() {
+        assert_eq!(split_query("/blog/hello-world"), ("/blog/hello-world", ""));
+        assert_eq!(split_query("/blog?sort=asc&page=2"), ("/blog", "sort=asc&page=2"));
+        assert_eq!(split_query("/blog?"), ("/blog", ""));
+    }
+
+    #[test]
+    fn test_ssr_context_query() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Query {
+            sort: String,
+            page: Option<u32>,
+        }
+
+        let context = SSRContext::new("sort=asc&page=2");
+        assert_eq!(
+            context.query::<Query>().unwrap(),
+            Query { sort: "asc".to_string(), page: Some(2) }
+        );
+
+        let empty = SSRContext::new("");
+        assert!(empty.query::<HashMap<String, String>>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_match_optional_catch_all_pattern() {
+        assert_eq!(
+            match_route_pattern("/docs/:::?:slug", "/docs").unwrap(),
+            Some(HashMap::new())
+        );
+
+        let mut expected = HashMap::new();
+        expected.insert(
+            "slug".to_string(),
+            RouteParam::Multi(vec!["a".to_string()]),
+        );
+        assert_eq!(
+            match_route_pattern("/docs/:::?:slug", "/docs/a").unwrap(),
+            Some(expected)
+        );
+    }
+}
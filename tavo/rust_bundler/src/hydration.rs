@@ -1,136 +1,224 @@
-use anyhow::{Result, Context};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use swc_common::{SourceMap, sync::Lrc};
-use swc_ecma_minifier::{optimize, ExtraOptions, MinifyOptions};
+use std::hash::{Hash, Hasher};
+use swc_common::sync::Lrc;
+use swc_common::{FileName, SourceMap};
 use swc_ecma_ast::*;
-use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax};
+use swc_ecma_visit::{VisitMut, VisitMutWith};
 
-use crate::bundler::Bundle;
+use crate::assets::EmittedAsset;
+use crate::bundler::LinkedModules;
 use crate::compiler::Compiler;
 
+/// A code-split chunk: `linked.dynamic`'s modules each become one of these
+/// instead of being inlined into the entry script.
+#[derive(Serialize, Deserialize)]
+pub struct Chunk {
+    /// File name the chunk is written under, keyed by a content hash so a
+    /// rebuild with unchanged output reuses the same URL (and a browser
+    /// cache keeps serving it).
+    pub id: String,
+    pub code: String,
+    /// Source map for `code`, present when `--sourcemap` was set and
+    /// `--inline-sourcemap` wasn't (in which case it's embedded in `code`
+    /// instead) — see `Compiler::generate_code`.
+    pub map: Option<String>,
+}
+
+/// `HydrationGenerator::generate`'s output: the entry script (with the chunk
+/// loader runtime prepended), the split-point chunks it can load on demand,
+/// and a manifest mapping each dynamic-import specifier to its chunk's file
+/// name so the loader knows what to fetch.
+#[derive(Serialize, Deserialize)]
+pub struct HydrationOutput {
+    pub entry: String,
+    /// Source map for `entry`, on the same terms as `Chunk::map`.
+    pub entry_map: Option<String>,
+    pub chunks: Vec<Chunk>,
+    pub manifest: String,
+    /// Static assets (images, fonts, ...) discovered while linking the
+    /// entry and its chunks that were too large to inline as a data URI —
+    /// see `bundler::Bundle::assets`.
+    pub assets: Vec<EmittedAsset>,
+}
+
+/// Runtime prepended to the entry script (after the `window.__TAVO_CHUNK_MANIFEST__`
+/// assignment `generate` also prepends) so `DynamicImportRewriter` rewriting
+/// a source `import(specifier)` into `window.__tavoLoadChunk(specifier)` has
+/// something real to call. A chunk is plain ESM (`export default ...`, same
+/// as any other module `Bundler::link` produces) with no idea this runtime
+/// exists, so loading it is a tiny inline `<script type="module">` that
+/// imports it by URL and hands its real namespace object to
+/// `window.__tavoChunkExports` — the only standards-compliant way to read an
+/// ES module's exports back out without re-parsing the chunk's own generated
+/// code.
+const CHUNK_LOADER_RUNTIME: &str = r#"
+(function () {
+  if (typeof window === 'undefined') { return; }
+  var manifest = window.__TAVO_CHUNK_MANIFEST__ || {};
+  window.__tavoChunkExports = window.__tavoChunkExports || {};
+  var pending = {};
+  window.__tavoLoadChunk = function (specifier) {
+    if (pending[specifier]) { return pending[specifier]; }
+    var url = manifest[specifier];
+    if (!url) {
+      return Promise.reject(new Error('No chunk registered for ' + specifier));
+    }
+    pending[specifier] = new Promise(function (resolve, reject) {
+      var script = document.createElement('script');
+      script.type = 'module';
+      script.textContent =
+        'import * as m from ' + JSON.stringify(url) + ';' +
+        'window.__tavoChunkExports[' + JSON.stringify(specifier) + '] = m;';
+      script.onload = function () { resolve(window.__tavoChunkExports[specifier]); };
+      script.onerror = function () { reject(new Error('Failed to load chunk ' + url)); };
+      document.head.appendChild(script);
+    });
+    return pending[specifier];
+  };
+})();
+"#;
+
+/// Parse the fixed expression `window.__tavoLoadChunk` into a real `Expr`,
+/// for `DynamicImportRewriter` to splice in as a call callee. Going through
+/// the real parser (rather than hand-building `Ident`/`MemberExpr` nodes)
+/// keeps this in step with whatever AST shape this SWC version actually
+/// expects, the same reasoning `parse_module_with_filename` follows in
+/// `compiler.rs`.
+fn parse_load_chunk_callee() -> Expr {
+    let source_map: Lrc<SourceMap> = Default::default();
+    let source_file = source_map.new_source_file(
+        Lrc::new(FileName::Custom("tavo_chunk_loader_callee".into())),
+        "window.__tavoLoadChunk".into(),
+    );
+    let lexer = Lexer::new(
+        Syntax::Es(Default::default()),
+        Default::default(),
+        StringInput::from(&*source_file),
+        None,
+    );
+    let mut parser = Parser::new_from(lexer);
+    *parser
+        .parse_expr()
+        .expect("fixed `window.__tavoLoadChunk` expression always parses")
+}
+
+/// Rewrites every dynamic `import(specifier)` call (the shape
+/// `find_dynamic_imports` in `bundler.rs` already looks for — a single
+/// string-literal argument) so it invokes `window.__tavoLoadChunk(specifier)`
+/// at runtime instead of the bare `Callee::Import` `swc_bundler` leaves
+/// behind. The specifier argument is left untouched, so it keeps matching
+/// the same string key `HydrationGenerator::generate` writes into
+/// `window.__TAVO_CHUNK_MANIFEST__`.
+struct DynamicImportRewriter {
+    callee: Expr,
+}
+
+impl VisitMut for DynamicImportRewriter {
+    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+        call.visit_mut_children_with(self);
+
+        let is_dynamic_import = matches!(call.callee, Callee::Import(_));
+        let has_string_specifier = matches!(
+            call.args.first().map(|arg| &*arg.expr),
+            Some(Expr::Lit(Lit::Str(_)))
+        );
+
+        if is_dynamic_import && has_string_specifier {
+            call.callee = Callee::Expr(Box::new(self.callee.clone()));
+        }
+    }
+}
+
+fn rewrite_dynamic_imports(module: &mut Module) {
+    let mut rewriter = DynamicImportRewriter {
+        callee: parse_load_chunk_callee(),
+    };
+    module.visit_mut_with(&mut rewriter);
+}
+
 pub struct HydrationGenerator {
-    source_map: Lrc<SourceMap>,
     compiler: Compiler,
 }
 
 impl HydrationGenerator {
-    pub fn new(source_map: Lrc<SourceMap>) -> Result<Self> {
+    pub fn new() -> Result<Self> {
         Ok(Self {
-            source_map,
             compiler: Compiler::new()?,
         })
     }
-    
-    pub async fn generate(&mut self, bundle: &Bundle) -> Result<String> {
-        // Combine all modules into a single script
-        let combined_code = self.combine_modules(bundle)?;
-        
-        // Parse the combined code
-        let module = self.compiler.parse_tsx(&combined_code, "hydration_bundle.js")?;
-        
-        // Minify the code
-        let minified = self.minify_module(module)?;
-        
-        Ok(minified)
+
+    /// Matches `Compiler::with_minify`: when set, every module this
+    /// generator emits (chunks and the entry alike) goes through
+    /// `Compiler::generate_code`'s compress + mangle + minified-codegen
+    /// path instead of plain pretty-printing.
+    pub fn set_minify(&mut self, minify: bool) {
+        self.compiler.minify = minify;
     }
-    
-    fn combine_modules(&self, bundle: &Bundle) -> Result<String> {
-        let mut combined = String::new();
-        
-        // Add React and ReactDOM imports at the top
-        combined.push_str(&format!(
-            r#"
-// React runtime for hydration
-const React = {{
-    createElement: function(type, props, ...children) {{
-        const element = document.createElement(type);
-        if (props) {{
-            for (const key in props) {{
-                if (key === 'className') {{
-                    element.className = props[key];
-                }} else if (key === 'onClick') {{
-                    element.onclick = props[key];
-                }} else if (key !== 'children') {{
-                    element.setAttribute(key, props[key]);
-                }}
-            }}
-        }}
-        
-        const allChildren = props && props.children 
-            ? [].concat(props.children, children).filter(Boolean)
-            : children.filter(Boolean);
-        
-        allChildren.forEach(child => {{
-            if (typeof child === 'string') {{
-                element.appendChild(document.createTextNode(child));
-            }} else {{
-                element.appendChild(child);
-            }}
-        }});
-        
-        return element;
-    }},
-    Fragment: function(props) {{
-        const fragment = document.createDocumentFragment();
-        if (props.children) {{
-            const children = Array.isArray(props.children) ? props.children : [props.children];
-            children.forEach(child => {{
-                if (typeof child === 'string') {{
-                    fragment.appendChild(document.createTextNode(child));
-                }} else {{
-                    fragment.appendChild(child);
-                }}
-            }});
-        }}
-        return fragment;
-    }}
-}};
-
-const ReactDOM = {{
-    hydrateRoot: function(container, element) {{
-        container.innerHTML = '';
-        container.appendChild(element);
-    }}
-}};
-
-"#
-        ));
-        
-        // Add all modules except the entry point
-        for (module_path, code) in &bundle.modules {
-            if module_path != &bundle.entry_point {
-                combined.push_str(&format!("// Module: {}\n", module_path));
-                combined.push_str(code);
-                combined.push_str("\n\n");
-            }
-        }
-        
-        // Add the entry point (hydration wrapper) last
-        if let Some(entry_code) = bundle.modules.get(&bundle.entry_point) {
-            combined.push_str("// Entry point (hydration)\n");
-            combined.push_str(entry_code);
-        }
-        
-        Ok(combined)
+
+    /// Matches `Compiler::with_sourcemap`: when set, every module this
+    /// generator emits gets a source map built alongside it.
+    pub fn set_sourcemap(&mut self, sourcemap: bool, inline: bool) {
+        self.compiler.sourcemap = sourcemap;
+        self.compiler.inline_sourcemap = inline;
     }
-    
-    fn minify_module(&self, module: Module) -> Result<String> {
-        let minified = optimize(
-            module,
-            self.source_map.clone(),
-            None,
-            None,
-            &MinifyOptions {
-                compress: Some(Default::default()),
-                mangle: Some(Default::default()),
-                ..Default::default()
-            },
-            &ExtraOptions {
-                unresolved_mark: swc_common::Mark::new(),
-                top_level_mark: swc_common::Mark::new(),
-            },
+
+    pub async fn generate(&mut self, linked: &LinkedModules) -> Result<HydrationOutput> {
+        let mut chunks = Vec::new();
+        let mut manifest_map = HashMap::new();
+
+        for (specifier, module) in &linked.dynamic {
+            // Each chunk is plain linked ESM with no idea the loader runtime
+            // exists, so any further `import()` it contains (a chunk that
+            // itself splits further) needs the same call-site rewrite the
+            // entry gets below.
+            let mut module = module.clone();
+            rewrite_dynamic_imports(&mut module);
+
+            // `linked.dynamic`'s modules are already linked `Module`s, same
+            // as `linked.entry` below, so they go through the same codegen
+            // path `self.compiler.minify`/`self.compiler.sourcemap` gate.
+            let (code, map) = self.compiler.generate_code(&module)?;
+            let id = format!("chunk-{}.js", content_hash(&code));
+            manifest_map.insert(specifier.clone(), id.clone());
+            chunks.push(Chunk { id, code, map });
+        }
+
+        let mut entry_module = linked.entry.clone();
+        rewrite_dynamic_imports(&mut entry_module);
+
+        // `linked.entry` is already a single linked AST, hoisted and
+        // dead-code eliminated by `swc_bundler`. The loader runtime (and the
+        // manifest it reads) is prepended after codegen, so neither is
+        // covered by `entry_map`'s mappings — acceptable, since they're a
+        // fixed, unminified few lines a source-mapped stack trace can still
+        // point at literally.
+        let (entry_code, entry_map) = self.compiler.generate_code(&entry_module)?;
+        let manifest_script = format!(
+            "window.__TAVO_CHUNK_MANIFEST__ = {};",
+            serde_json::to_string(&manifest_map)?
         );
-        
-        self.compiler.generate_code(&minified)
+        let entry = format!(
+            "{}\n{}\n{}",
+            manifest_script, CHUNK_LOADER_RUNTIME, entry_code
+        );
+        let manifest = serde_json::to_string_pretty(&manifest_map)?;
+
+        Ok(HydrationOutput {
+            entry,
+            entry_map,
+            chunks,
+            manifest,
+            assets: linked.assets.clone(),
+        })
     }
-}
\ No newline at end of file
+}
+
+fn content_hash(code: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    code.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
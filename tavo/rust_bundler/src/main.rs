@@ -1,64 +1,207 @@
 use clap::Parser;
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+mod assets;
+mod cache;
 mod cli;
 mod compiler;
 mod bundler;
+mod dev;
+mod error;
+mod fs_router;
 mod ssr;
 mod hydration;
+mod tsconfig;
 mod utils;
 
+use assets::EmittedAsset;
 use cli::Args;
-use compiler::Compiler;
+use compiler::{Compiler, TransformSettings};
+use hydration::HydrationOutput;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
-    
-    let mut compiler = Compiler::new()?;
-    
+
+    if args.dev {
+        let transform_settings = TransformSettings::from_args(&args).with_tsconfig_near(&args.app_dir);
+        return dev::run(args.app_dir, args.port, transform_settings).await;
+    }
+
+    let entry = args.entry.clone().ok_or_else(|| {
+        anyhow::anyhow!("--entry is required unless --dev is set")
+    })?;
+
+    let transform_settings = TransformSettings::from_args(&args)
+        .with_tsconfig_near(entry.parent().unwrap_or_else(|| Path::new(".")));
+    let mut compiler = Compiler::with_transform_settings(transform_settings)?
+        .with_minify(args.minify)
+        .with_sourcemap(args.sourcemap, args.inline_sourcemap)
+        .with_cache(&args.cache_dir, args.no_cache)?;
+
     match (args.ssr, args.hydrate, args.out.as_ref()) {
         // SSR only
         (true, false, None) => {
-            let html = compiler.render_ssr(&args.entry).await?;
+            let (html, _assets) = compiler.render_ssr(&entry).await?;
             println!("{}", html);
         }
-        
+
         // Hydration only
         (false, true, None) => {
-            let js = compiler.generate_hydration_script(&args.entry).await?;
-            println!("{}", js);
+            let hydration = compiler.generate_hydration_script(&entry).await?;
+            print_hydration_output(&hydration);
         }
-        
+
         // Both SSR and hydration to file
         (true, true, Some(output)) => {
-            let html = compiler.render_ssr(&args.entry).await?;
-            let js = compiler.generate_hydration_script(&args.entry).await?;
-            let full_html = compiler.combine_html_and_script(&html, &js)?;
-            std::fs::write(output, full_html)?;
+            let (html, ssr_assets) = compiler.render_ssr(&entry).await?;
+            let hydration = compiler.generate_hydration_script(&entry).await?;
+            let full_html = compiler.combine_html_and_script(&html, &hydration.entry)?;
+            std::fs::write(output, &full_html)?;
+            if args.compress {
+                write_compressed_siblings(output, full_html.as_bytes())?;
+            }
+            // The entry's map is written as `<output>.map`. Nothing in
+            // `full_html` refers to it: `generate_code` only emits a
+            // `//# sourceMappingURL=` comment for an inline map.
+            write_source_map_sibling(output, hydration.entry_map.as_deref())?;
+            write_hydration_chunks(output, &hydration, args.compress)?;
+            write_emitted_assets(output, ssr_assets.iter().chain(&hydration.assets))?;
             eprintln!("Generated: {}", output.display());
         }
-        
+
         // SSR to file
         (true, false, Some(output)) => {
-            let html = compiler.render_ssr(&args.entry).await?;
-            std::fs::write(output, html)?;
+            let (html, assets) = compiler.render_ssr(&entry).await?;
+            std::fs::write(output, &html)?;
+            if args.compress {
+                write_compressed_siblings(output, html.as_bytes())?;
+            }
+            write_emitted_assets(output, assets.iter())?;
             eprintln!("Generated: {}", output.display());
         }
-        
+
         // Hydration to file
         (false, true, Some(output)) => {
-            let js = compiler.generate_hydration_script(&args.entry).await?;
-            std::fs::write(output, js)?;
+            let hydration = compiler.generate_hydration_script(&entry).await?;
+            std::fs::write(output, &hydration.entry)?;
+            if args.compress {
+                write_compressed_siblings(output, hydration.entry.as_bytes())?;
+            }
+            write_source_map_sibling(output, hydration.entry_map.as_deref())?;
+            write_hydration_chunks(output, &hydration, args.compress)?;
+            write_emitted_assets(output, hydration.assets.iter())?;
             eprintln!("Generated: {}", output.display());
         }
-        
+
         _ => {
             eprintln!("Error: Must specify either --ssr, --hydrate, or both");
             std::process::exit(1);
         }
     }
-    
+
+    Ok(())
+}
+
+fn print_hydration_output(hydration: &HydrationOutput) {
+    println!("{}", hydration.entry);
+    for chunk in &hydration.chunks {
+        println!("\n// chunk: {}\n{}", chunk.id, chunk.code);
+    }
+    println!("\n// chunk manifest\n{}", hydration.manifest);
+}
+
+/// Writes each split-point chunk as a sibling of `output`, named by its
+/// content hash, plus a `<stem>.manifest.json` mapping dynamic-import
+/// specifiers to those chunk file names — mirrors `write_source_map_sibling`
+/// writing a source map as a sibling of the JS it was generated from. When
+/// `compress` is set, each chunk also gets `.gz`/`.br` siblings (the
+/// manifest doesn't; it's small JSON a host gzips on the fly).
+fn write_hydration_chunks(output: &Path, hydration: &HydrationOutput, compress: bool) -> Result<()> {
+    let dir = output.parent().unwrap_or_else(|| Path::new("."));
+
+    for chunk in &hydration.chunks {
+        let chunk_path = dir.join(&chunk.id);
+        std::fs::write(&chunk_path, &chunk.code)?;
+        if compress {
+            write_compressed_siblings(&chunk_path, chunk.code.as_bytes())?;
+        }
+        write_source_map_sibling(&chunk_path, chunk.map.as_deref())?;
+    }
+
+    if !hydration.chunks.is_empty() {
+        let manifest_name = format!(
+            "{}.manifest.json",
+            output.file_stem().and_then(|s| s.to_str()).unwrap_or("hydration")
+        );
+        std::fs::write(dir.join(manifest_name), &hydration.manifest)?;
+    }
+
+    Ok(())
+}
+
+/// Writes each static asset `assets::resolve_asset_url` decided was too
+/// large to inline, under `<output's directory>` + its `/_tavo/assets/...`
+/// URL — mirrors `write_hydration_chunks` writing chunks as siblings of
+/// `output`, just rooted one level deeper to match the URL the generated
+/// module actually requests.
+fn write_emitted_assets<'a>(
+    output: &Path,
+    assets: impl Iterator<Item = &'a EmittedAsset>,
+) -> Result<()> {
+    let dir = output.parent().unwrap_or_else(|| Path::new("."));
+
+    for asset in assets {
+        let relative = asset.url.trim_start_matches('/');
+        let asset_path = dir.join(relative);
+        if let Some(parent) = asset_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&asset_path, &asset.bytes)?;
+    }
+
+    Ok(())
+}
+
+/// Writes `map` as `<path>.map` when present — absent whenever `--sourcemap`
+/// wasn't passed, or `--inline-sourcemap` embedded it in the code instead
+/// (see `Compiler::generate_code`), in which case this is a no-op.
+fn write_source_map_sibling(path: &Path, map: Option<&str>) -> Result<()> {
+    let Some(map) = map else {
+        return Ok(());
+    };
+
+    let mut map_name = path.as_os_str().to_os_string();
+    map_name.push(".map");
+    std::fs::write(PathBuf::from(map_name), map)?;
+
+    Ok(())
+}
+
+/// Gzip- and brotli-compress `contents`, writing them as `<path>.gz` and
+/// `<path>.br` so a static host (or a reverse proxy in front of one) can
+/// serve the precompressed artifact directly instead of compressing it on
+/// every request.
+fn write_compressed_siblings(path: &Path, contents: &[u8]) -> Result<()> {
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    let mut gz_name = path.as_os_str().to_os_string();
+    gz_name.push(".gz");
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(contents)?;
+    std::fs::write(PathBuf::from(gz_name), encoder.finish()?)?;
+
+    let mut br_name = path.as_os_str().to_os_string();
+    br_name.push(".br");
+    let mut br_out = Vec::new();
+    brotli::BrotliCompress(
+        &mut std::io::Cursor::new(contents),
+        &mut br_out,
+        &brotli::enc::BrotliEncoderParams::default(),
+    )?;
+    std::fs::write(PathBuf::from(br_name), br_out)?;
+
     Ok(())
 }
\ No newline at end of file
@@ -1,40 +1,81 @@
-use anyhow::{Result, Context};
+use anyhow::{Context, Result};
 use boa_engine::{Context as BoaContext, Source};
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use swc_common::{sync::Lrc, SourceMap};
+use swc_ecma_ast::Module;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
 
 use crate::bundler::Bundle;
+use crate::utils::read_file_content;
 
+/// `build.rs` copies `node_modules/react/umd` and
+/// `node_modules/react-dom/umd` into this directory (flattened, so both
+/// packages' files land side by side) next to the crate manifest.
+const VENDOR_REACT_UMD: &str = "react.production.min.js";
+const VENDOR_REACT_DOM_SERVER_UMD: &str = "react-dom-server.browser.production.min.js";
+
+/// `&mut self` access to `context` is already strictly sequential — `Compiler`
+/// holds one `SSRRenderer` and calls `render` one bundle at a time — so a
+/// pool of contexts would sit idle with nothing to parallelize against.
+/// What actually repeats across calls in practice (the dev server rebuilding
+/// a route on every save, often with no relevant change to this bundle) is
+/// re-running the exact same generated JS through React's SSR renderer, so
+/// `last_render` caches that instead: skip the Boa `eval` of an
+/// unchanged bundle — by far the most expensive part of `render` — and
+/// reuse the HTML it produced last time.
 pub struct SSRRenderer {
     context: BoaContext,
+    source_map: Lrc<SourceMap>,
+    /// Content hash of the last bundle's generated code, paired with the
+    /// HTML it rendered to.
+    last_render: Option<(u64, String)>,
 }
 
 impl SSRRenderer {
-    pub fn new() -> Result<Self> {
+    pub fn new(source_map: Lrc<SourceMap>) -> Result<Self> {
         let mut context = BoaContext::default();
-        
+
         // Setup React and ReactDOMServer globals
         Self::setup_react_globals(&mut context)?;
-        
-        Ok(Self { context })
+
+        Ok(Self { context, source_map, last_render: None })
     }
-    
+
     pub async fn render(&mut self, bundle: &Bundle) -> Result<String> {
-        // Execute all modules in dependency order
-        for (module_path, code) in &bundle.modules {
-            if module_path != &bundle.entry_point {
-                self.execute_module(module_path, code)?;
+        // `bundle.module` is already a single linked AST resolved by
+        // `swc_bundler`, so there's just one module to generate and execute.
+        let code = self.generate_code(&bundle.module)?;
+        let hash = content_hash(&code);
+
+        if let Some((cached_hash, html)) = &self.last_render {
+            if *cached_hash == hash {
+                return Ok(html.clone());
             }
         }
-        
-        // Execute entry point and render
-        if let Some(entry_code) = bundle.modules.get(&bundle.entry_point) {
-            self.execute_module(&bundle.entry_point, entry_code)?;
-            self.render_to_string()
-        } else {
-            Err(anyhow::anyhow!("Entry point not found in bundle"))
+
+        self.execute_module(&bundle.entry_name, &code)?;
+        let html = self.render_to_string()?;
+        self.last_render = Some((hash, html.clone()));
+        Ok(html)
+    }
+
+    fn generate_code(&self, module: &Module) -> Result<String> {
+        let mut buf = Vec::new();
+        {
+            let writer = JsWriter::new(self.source_map.clone(), "\n", &mut buf, None);
+            let mut emitter = Emitter {
+                cfg: swc_ecma_codegen::Config::default(),
+                cm: self.source_map.clone(),
+                comments: None,
+                wr: writer,
+            };
+            emitter.emit_module(module)?;
         }
+        String::from_utf8(buf).context("Generated code is not valid UTF-8")
     }
-    
+
     fn execute_module(&mut self, _module_path: &str, code: &str) -> Result<()> {
         self.context
             .eval(Source::from_bytes(code))
@@ -74,8 +115,62 @@ impl SSRRenderer {
             .context("Failed to convert render result to string")
     }
     
+    /// Prefer the genuine React build `build.rs` vendored from
+    /// `node_modules`; whenever loading it fails (as in a checkout that
+    /// never ran `npm install`, and so never got a `vendor/` directory) fall
+    /// back to the string-concat mock below.
     fn setup_react_globals(context: &mut BoaContext) -> Result<()> {
-        // Mock React implementation for SSR
+        match Self::load_vendored_react(context) {
+            Ok(()) => Ok(()),
+            Err(_) => {
+                // The mock assigns to `window.React`, and a missing vendor
+                // file fails `load_vendored_react` before its shim runs.
+                context
+                    .eval(Source::from_bytes("var window = globalThis;"))
+                    .map_err(|e| anyhow::anyhow!("Failed to shim browser globals: {}", e))?;
+                Self::setup_mock_react_globals(context)
+            }
+        }
+    }
+
+    /// Load the real `react.production.min.js` and
+    /// `react-dom-server.browser.production.min.js` UMD bundles from
+    /// `vendor/` into `context`, after shimming the `window`/`globalThis`
+    /// and `process.env.NODE_ENV` globals they check for at load time. Boa
+    /// already exposes `globalThis`; `window` is aliased to it since the
+    /// render script below (like the mock it replaces) reads bare `React`
+    /// rather than `window.React`, and the UMD loader pattern writes to
+    /// whichever global object it's handed.
+    fn load_vendored_react(context: &mut BoaContext) -> Result<()> {
+        let vendor_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("vendor");
+        let react_src = read_file_content(&vendor_dir.join(VENDOR_REACT_UMD))?;
+        let react_dom_server_src = read_file_content(&vendor_dir.join(VENDOR_REACT_DOM_SERVER_UMD))?;
+
+        context
+            .eval(Source::from_bytes(
+                r#"
+                    var window = globalThis;
+                    var process = { env: { NODE_ENV: "production" } };
+                "#,
+            ))
+            .map_err(|e| anyhow::anyhow!("Failed to shim browser globals: {}", e))?;
+
+        context
+            .eval(Source::from_bytes(&react_src))
+            .map_err(|e| anyhow::anyhow!("Failed to load vendored react UMD bundle: {}", e))?;
+
+        context
+            .eval(Source::from_bytes(&react_dom_server_src))
+            .map_err(|e| anyhow::anyhow!("Failed to load vendored react-dom-server UMD bundle: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Toy `React.createElement`/`ReactDOMServer.renderToString` used only
+    /// when `vendor/` hasn't been populated: no keys, no context, no hooks,
+    /// and naive string escaping. Good enough to smoke-test the render
+    /// pipeline without `node_modules` installed, nothing more.
+    fn setup_mock_react_globals(context: &mut BoaContext) -> Result<()> {
         let react_mock = r#"
             window.React = {
                 createElement: function(type, props, ...children) {
@@ -143,7 +238,13 @@ impl SSRRenderer {
         context
             .eval(Source::from_bytes(react_mock))
             .context("Failed to setup React globals")?;
-        
+
         Ok(())
     }
+}
+
+fn content_hash(code: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    code.hash(&mut hasher);
+    hasher.finish()
 }
\ No newline at end of file
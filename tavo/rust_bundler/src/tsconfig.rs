@@ -0,0 +1,212 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use swc_ecma_ast::EsVersion;
+use swc_ecma_transforms::react::Runtime;
+
+/// `compilerOptions` keys that influence type-checking but not transpile-only
+/// emit, so they're safe to ignore here. Mirrors Deno's
+/// `IGNORED_COMPILER_OPTIONS`.
+const IGNORED_COMPILER_OPTIONS: &[&str] = &[
+    "allowUnreachableCode",
+    "allowUnusedLabels",
+    "alwaysStrict",
+    "baseUrl",
+    "declaration",
+    "declarationDir",
+    "declarationMap",
+    "downlevelIteration",
+    "incremental",
+    "isolatedModules",
+    "lib",
+    "noEmit",
+    "noFallthroughCasesInSwitch",
+    "noImplicitAny",
+    "noImplicitReturns",
+    "noImplicitThis",
+    "noStrictGenericChecks",
+    "noUnusedLocals",
+    "noUnusedParameters",
+    "paths",
+    "plugins",
+    "preserveConstEnums",
+    "removeComments",
+    "rootDir",
+    "rootDirs",
+    "skipLibCheck",
+    "sourceRoot",
+    "strict",
+    "strictBindCallApply",
+    "strictFunctionTypes",
+    "strictNullChecks",
+    "strictPropertyInitialization",
+    "suppressExcessPropertyErrors",
+    "suppressImplicitAnyIndexErrors",
+    "types",
+    "typeRoots",
+];
+
+/// The subset of `tsconfig.json`'s `compilerOptions` that actually change
+/// transpile output, resolved to the settings the rest of the compiler
+/// understands (`Runtime`, pragma, target `EsVersion`).
+#[derive(Debug, Clone)]
+pub struct TsConfigResolved {
+    pub runtime: Runtime,
+    pub import_source: Option<String>,
+    pub pragma: Option<String>,
+    pub pragma_frag: Option<String>,
+    pub target: EsVersion,
+}
+
+impl Default for TsConfigResolved {
+    fn default() -> Self {
+        Self {
+            runtime: Runtime::Classic,
+            import_source: None,
+            pragma: None,
+            pragma_frag: None,
+            target: EsVersion::Es2020,
+        }
+    }
+}
+
+/// Walk up from `start_dir` looking for the nearest `tsconfig.json`, follow
+/// its `extends` chain (each parent merged underneath the child, so the
+/// child's keys win), and resolve `compilerOptions` into [`TsConfigResolved`].
+/// Returns `Ok(None)` if no `tsconfig.json` is found anywhere above
+/// `start_dir`.
+pub fn load_tsconfig(start_dir: &Path) -> Result<Option<TsConfigResolved>> {
+    let Some(path) = find_tsconfig(start_dir) else {
+        return Ok(None);
+    };
+
+    let merged = load_and_merge(&path)?;
+    Ok(Some(resolve_compiler_options(&merged)))
+}
+
+fn find_tsconfig(start_dir: &Path) -> Option<PathBuf> {
+    let mut dir = Some(start_dir);
+    while let Some(current) = dir {
+        let candidate = current.join("tsconfig.json");
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        dir = current.parent();
+    }
+    None
+}
+
+/// Load `path`, then recursively load and merge whatever it `extends`
+/// (relative to `path`'s own directory), with `path`'s own keys taking
+/// priority over anything inherited.
+fn load_and_merge(path: &Path) -> Result<Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read tsconfig: {}", path.display()))?;
+    let config: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse tsconfig: {}", path.display()))?;
+
+    let mut merged = match config.get("extends").and_then(Value::as_str) {
+        Some(extends) => {
+            let parent_path = path
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(extends);
+            load_and_merge(&parent_path).unwrap_or(Value::Object(Default::default()))
+        }
+        None => Value::Object(Default::default()),
+    };
+
+    merge_json(&mut merged, &config);
+    Ok(merged)
+}
+
+/// Recursively merge `overlay` on top of `base`, overwriting scalar values
+/// and descending into objects key-by-key.
+fn merge_json(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (Value::Object(base_map), Value::Object(overlay_map)) => {
+            for (key, value) in overlay_map {
+                merge_json(
+                    base_map.entry(key.clone()).or_insert(Value::Null),
+                    value,
+                );
+            }
+        }
+        (base_slot, overlay_value) => {
+            *base_slot = overlay_value.clone();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+struct CompilerOptions {
+    jsx: Option<String>,
+    #[serde(rename = "jsxImportSource")]
+    jsx_import_source: Option<String>,
+    #[serde(rename = "jsxFactory")]
+    jsx_factory: Option<String>,
+    #[serde(rename = "jsxFragmentFactory")]
+    jsx_fragment_factory: Option<String>,
+    target: Option<String>,
+}
+
+fn resolve_compiler_options(merged: &Value) -> TsConfigResolved {
+    let options: CompilerOptions = merged
+        .get("compilerOptions")
+        .cloned()
+        .map(|value| serde_json::from_value(filter_ignored(value)).unwrap_or_default())
+        .unwrap_or_default();
+
+    let mut resolved = TsConfigResolved::default();
+
+    match options.jsx.as_deref() {
+        Some("react-jsx") | Some("react-jsxdev") => {
+            resolved.runtime = Runtime::Automatic;
+            resolved.import_source = options.jsx_import_source;
+        }
+        Some("react") | None => {
+            resolved.runtime = Runtime::Classic;
+            resolved.pragma = options.jsx_factory;
+            resolved.pragma_frag = options.jsx_fragment_factory;
+        }
+        Some(_other) => {
+            // Unknown `jsx` mode (e.g. "preserve"): fall back to classic
+            // rather than failing the whole compile over a typo.
+            resolved.runtime = Runtime::Classic;
+        }
+    }
+
+    if let Some(target) = options.target.as_deref() {
+        resolved.target = es_version_from_target(target);
+    }
+
+    resolved
+}
+
+/// Drop keys from `IGNORED_COMPILER_OPTIONS` before deserializing, so an
+/// unrecognized type-checking flag in a project's `tsconfig.json` never
+/// breaks transpile-only emit.
+fn filter_ignored(mut value: Value) -> Value {
+    if let Value::Object(map) = &mut value {
+        map.retain(|key, _| !IGNORED_COMPILER_OPTIONS.contains(&key.as_str()));
+    }
+    value
+}
+
+fn es_version_from_target(target: &str) -> EsVersion {
+    match target.to_ascii_lowercase().as_str() {
+        "es3" => EsVersion::Es3,
+        "es5" => EsVersion::Es5,
+        "es6" | "es2015" => EsVersion::Es2015,
+        "es2016" => EsVersion::Es2016,
+        "es2017" => EsVersion::Es2017,
+        "es2018" => EsVersion::Es2018,
+        "es2019" => EsVersion::Es2019,
+        "es2020" => EsVersion::Es2020,
+        "es2021" => EsVersion::Es2021,
+        "es2022" => EsVersion::Es2022,
+        "esnext" => EsVersion::EsNext,
+        _ => EsVersion::Es2020,
+    }
+}
@@ -1,15 +1,16 @@
 use anyhow::{Result, Context};
 use std::path::{Path, PathBuf};
 use std::fs;
+use serde_json::Value;
 
 pub fn resolve_import(import_path: &str, current_file: &Path) -> Result<PathBuf> {
     let current_dir = current_file.parent()
         .context("Failed to get parent directory")?;
-    
+
     // Handle relative imports
     if import_path.starts_with("./") || import_path.starts_with("../") {
         let mut resolved = current_dir.join(import_path);
-        
+
         // Try different extensions
         let extensions = ["", ".tsx", ".ts", ".jsx", ".js"];
         for ext in &extensions {
@@ -18,12 +19,12 @@ pub fn resolve_import(import_path: &str, current_file: &Path) -> Result<PathBuf>
             } else {
                 resolved.with_extension(&ext[1..]) // Remove the dot
             };
-            
+
             if path_with_ext.exists() {
                 return Ok(path_with_ext);
             }
         }
-        
+
         // Try as directory with index file
         for ext in &[".tsx", ".ts", ".jsx", ".js"] {
             let index_path = resolved.join(format!("index{}", ext));
@@ -31,13 +32,160 @@ pub fn resolve_import(import_path: &str, current_file: &Path) -> Result<PathBuf>
                 return Ok(index_path);
             }
         }
-        
+
         return Err(anyhow::anyhow!("Could not resolve import: {}", import_path));
     }
-    
-    // Handle node_modules imports (simplified - just return the import path)
-    // In a real implementation, you'd resolve these from node_modules
-    Ok(PathBuf::from(import_path))
+
+    resolve_bare_specifier(import_path, current_dir)
+}
+
+/// Resolve a bare specifier (`react`, `@scope/pkg`, `pkg/sub/path`) the way
+/// Node (and `swc_ecma_loader::resolvers::node::NodeModulesResolver`) does:
+/// walk upward from `current_dir` looking for a `node_modules/<pkg>`
+/// directory, then resolve that package's entry point from its
+/// `package.json`.
+fn resolve_bare_specifier(import_path: &str, current_dir: &Path) -> Result<PathBuf> {
+    let (pkg_name, subpath) = split_specifier(import_path);
+
+    let mut dir = Some(current_dir);
+    while let Some(d) = dir {
+        let candidate = d.join("node_modules").join(&pkg_name);
+        if candidate.is_dir() {
+            return resolve_package_entry(&candidate, &subpath).with_context(|| {
+                format!("Failed to resolve '{}' in node_modules", import_path)
+            });
+        }
+        dir = d.parent();
+    }
+
+    Err(anyhow::anyhow!(
+        "Could not resolve '{}': no node_modules/{} found above {}",
+        import_path,
+        pkg_name,
+        current_dir.display()
+    ))
+}
+
+/// Split a bare specifier into its package name and subpath, honoring the
+/// `@scope/name` form (whose first two `/`-separated segments both belong to
+/// the package name).
+fn split_specifier(specifier: &str) -> (String, String) {
+    let mut parts = specifier.splitn(if specifier.starts_with('@') { 3 } else { 2 }, '/');
+    let pkg_name = if specifier.starts_with('@') {
+        let scope = parts.next().unwrap_or("");
+        let name = parts.next().unwrap_or("");
+        format!("{}/{}", scope, name)
+    } else {
+        parts.next().unwrap_or("").to_string()
+    };
+    let subpath = parts.next().unwrap_or("").to_string();
+    (pkg_name, subpath)
+}
+
+/// Resolve `subpath` (empty for the package root) within `pkg_dir` per its
+/// `package.json`: the `exports` field first (honoring the `import`/
+/// `default` conditions and `*` subpath patterns), then `module`, then
+/// `main`, then an `index.{tsx,ts,jsx,js}` fallback.
+fn resolve_package_entry(pkg_dir: &Path, subpath: &str) -> Result<PathBuf> {
+    let pkg_json_path = pkg_dir.join("package.json");
+    let pkg_json: Value = if pkg_json_path.is_file() {
+        let content = fs::read_to_string(&pkg_json_path)
+            .with_context(|| format!("Failed to read {}", pkg_json_path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", pkg_json_path.display()))?
+    } else {
+        Value::Null
+    };
+
+    let export_key = if subpath.is_empty() { ".".to_string() } else { format!("./{}", subpath) };
+
+    if let Some(exports) = pkg_json.get("exports") {
+        if let Some(target) = resolve_exports_field(exports, &export_key) {
+            let candidate = pkg_dir.join(target.trim_start_matches("./"));
+            if let Some(found) = probe_file_or_index(&candidate) {
+                return Ok(found);
+            }
+        }
+    }
+
+    if subpath.is_empty() {
+        for field in ["module", "main"] {
+            if let Some(entry) = pkg_json.get(field).and_then(Value::as_str) {
+                if let Some(found) = probe_file_or_index(&pkg_dir.join(entry)) {
+                    return Ok(found);
+                }
+            }
+        }
+        if let Some(found) = probe_file_or_index(pkg_dir) {
+            return Ok(found);
+        }
+    } else if let Some(found) = probe_file_or_index(&pkg_dir.join(subpath)) {
+        return Ok(found);
+    }
+
+    Err(anyhow::anyhow!(
+        "No entry point found for '{}' in {}",
+        export_key,
+        pkg_dir.display()
+    ))
+}
+
+/// Walk the `exports` map (or bare string/conditions object) for the entry
+/// matching `key`, falling back to `*` subpath patterns the same way Node's
+/// exports resolution does.
+fn resolve_exports_field(exports: &Value, key: &str) -> Option<String> {
+    match exports {
+        Value::String(target) if key == "." => Some(target.clone()),
+        Value::Object(map) => {
+            if let Some(target) = map.get(key) {
+                return resolve_conditions(target);
+            }
+            if key == "." && !map.keys().any(|k| k.starts_with('.')) {
+                return resolve_conditions(exports);
+            }
+            map.iter().find_map(|(pattern, target)| {
+                let prefix = pattern.strip_suffix('*')?;
+                let rest = key.strip_prefix(prefix)?;
+                resolve_conditions(target).map(|t| t.replacen('*', rest, 1))
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Resolve a conditional exports target down to a path string, preferring
+/// the `import` condition (this codebase only ever emits ESM) and falling
+/// back to `default`.
+fn resolve_conditions(value: &Value) -> Option<String> {
+    match value {
+        Value::String(target) => Some(target.clone()),
+        Value::Object(map) => ["import", "default"]
+            .iter()
+            .find_map(|cond| map.get(*cond).and_then(resolve_conditions)),
+        _ => None,
+    }
+}
+
+/// Try `base` as a file (optionally adding each known extension), then as a
+/// directory with an `index.{tsx,ts,jsx,js}` file — the same probing the
+/// relative-import branch above does.
+fn probe_file_or_index(base: &Path) -> Option<PathBuf> {
+    if base.is_file() {
+        return Some(base.to_path_buf());
+    }
+    for ext in &[".tsx", ".ts", ".jsx", ".js"] {
+        let candidate = base.with_extension(&ext[1..]);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+    for ext in &[".tsx", ".ts", ".jsx", ".js"] {
+        let index_path = base.join(format!("index{}", ext));
+        if index_path.is_file() {
+            return Some(index_path);
+        }
+    }
+    None
 }
 
 pub fn read_file_content(path: &Path) -> Result<String> {
@@ -72,4 +220,61 @@ mod tests {
         let resolved = resolve_import("./components/App", &current_file).unwrap();
         assert_eq!(resolved, target_file);
     }
+
+    #[test]
+    fn test_resolve_bare_specifier_via_main_field() {
+        let temp_dir = tempdir().unwrap();
+        let current_file = temp_dir.path().join("src").join("App.tsx");
+        let pkg_dir = temp_dir.path().join("node_modules").join("left-pad");
+
+        fs::create_dir_all(&pkg_dir).unwrap();
+        fs::write(pkg_dir.join("package.json"), r#"{"main": "index.js"}"#).unwrap();
+        fs::write(pkg_dir.join("index.js"), "module.exports = function leftPad() {}").unwrap();
+
+        let resolved = resolve_import("left-pad", &current_file).unwrap();
+        assert_eq!(resolved, pkg_dir.join("index.js"));
+    }
+
+    #[test]
+    fn test_resolve_scoped_bare_specifier_via_exports_field() {
+        let temp_dir = tempdir().unwrap();
+        let current_file = temp_dir.path().join("src").join("App.tsx");
+        let pkg_dir = temp_dir.path().join("node_modules").join("@scope").join("widgets");
+
+        fs::create_dir_all(&pkg_dir).unwrap();
+        fs::write(
+            pkg_dir.join("package.json"),
+            r#"{"exports": {".": {"import": "./esm/index.js", "default": "./cjs/index.js"}}}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(pkg_dir.join("esm")).unwrap();
+        fs::write(pkg_dir.join("esm").join("index.js"), "export default {}").unwrap();
+
+        let resolved = resolve_import("@scope/widgets", &current_file).unwrap();
+        assert_eq!(resolved, pkg_dir.join("esm").join("index.js"));
+    }
+
+    #[test]
+    fn test_resolve_bare_specifier_walks_up_to_ancestor_node_modules() {
+        let temp_dir = tempdir().unwrap();
+        let current_file = temp_dir.path().join("src").join("components").join("App.tsx");
+        let pkg_dir = temp_dir.path().join("node_modules").join("react");
+
+        fs::create_dir_all(current_file.parent().unwrap()).unwrap();
+        fs::create_dir_all(&pkg_dir).unwrap();
+        fs::write(pkg_dir.join("package.json"), r#"{"main": "index.js"}"#).unwrap();
+        fs::write(pkg_dir.join("index.js"), "module.exports = {}").unwrap();
+
+        let resolved = resolve_import("react", &current_file).unwrap();
+        assert_eq!(resolved, pkg_dir.join("index.js"));
+    }
+
+    #[test]
+    fn test_resolve_unknown_bare_specifier_errors() {
+        let temp_dir = tempdir().unwrap();
+        let current_file = temp_dir.path().join("src").join("App.tsx");
+        fs::create_dir_all(current_file.parent().unwrap()).unwrap();
+
+        assert!(resolve_import("not-a-real-package", &current_file).is_err());
+    }
 }
\ No newline at end of file